@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// A declarative opcode-weight entry: the Rust expression that constructs
+/// the decoded value, and how many of the 256 byte values (or 16 nibble
+/// values, for operands) map to it. Entries tile the input range in order,
+/// so reordering this table changes which inputs decode to which opcode --
+/// a consensus-breaking change for the mining ISA.
+type Spec = &'static [(&'static str, u32)];
+
+/// Opcode distribution for the original, integer-only ISA.
+const ISA_V1: Spec = &[
+    ("Instr::Op3(Op3::Add)", 40),
+    ("Instr::Op3(Op3::Mul)", 40),
+    ("Instr::Op3(Op3::MulH)", 16),
+    ("Instr::Op3(Op3::Div)", 16),
+    ("Instr::Op3(Op3::Mod)", 16),
+    ("Instr::Op2(Op2::ISqrt)", 10),
+    ("Instr::Op2(Op2::BitRev)", 10),
+    ("Instr::Op3(Op3::Xor)", 40),
+    ("Instr::Op2(Op2::RotL)", 16),
+    ("Instr::Op2(Op2::RotR)", 16),
+    ("Instr::Op2(Op2::Neg)", 20),
+    ("Instr::Op3(Op3::And)", 8),
+];
+
+/// Opcode distribution for the floating-point generation: `Xor`'s share is
+/// narrowed from 40 to 24 to make room for 4 values each of
+/// `AddF`/`SubF`/`MulF`/`DivF`.
+const ISA_V2: Spec = &[
+    ("Instr::Op3(Op3::Add)", 40),
+    ("Instr::Op3(Op3::Mul)", 40),
+    ("Instr::Op3(Op3::MulH)", 16),
+    ("Instr::Op3(Op3::Div)", 16),
+    ("Instr::Op3(Op3::Mod)", 16),
+    ("Instr::Op2(Op2::ISqrt)", 10),
+    ("Instr::Op2(Op2::BitRev)", 10),
+    ("Instr::Op3(Op3::Xor)", 24),
+    ("Instr::Op3(Op3::AddF)", 4),
+    ("Instr::Op3(Op3::SubF)", 4),
+    ("Instr::Op3(Op3::MulF)", 4),
+    ("Instr::Op3(Op3::DivF)", 4),
+    ("Instr::Op2(Op2::RotL)", 16),
+    ("Instr::Op2(Op2::RotR)", 16),
+    ("Instr::Op2(Op2::Neg)", 20),
+    ("Instr::Op3(Op3::And)", 8),
+];
+
+/// The top 8 byte values (248..=255) are always reserved for `Hash(0..8)`,
+/// one distinct sub-opcode per value, on every generation.
+const HASH_WEIGHT: u32 = 8;
+
+/// Operand-kind distribution over the 16 possible 4-bit operand-encoding
+/// nibbles.
+const OPERAND_SPEC: Spec = &[
+    ("Operand::Reg", 5),
+    ("Operand::Memory", 4),
+    ("Operand::Literal", 4),
+    ("Operand::Special1", 1),
+    ("Operand::Special2", 2),
+];
+
+/// Render `spec` into `match` arms covering `0..(256 - HASH_WEIGHT)`,
+/// followed by one arm per `Hash` sub-opcode covering the rest. Panics
+/// (failing the build) if the weights don't tile exactly 256 values, so a
+/// typo in the table breaks `cargo build` instead of silently shipping a
+/// gap or overlap in the opcode space.
+fn generate_opcode_match(spec: Spec) -> String {
+    let declared: u32 = spec.iter().map(|(_, w)| w).sum::<u32>() + HASH_WEIGHT;
+    assert_eq!(declared, 256, "opcode spec must tile exactly 256 byte values, got {declared}");
+
+    let mut out = String::new();
+    let mut lo: u32 = 0;
+    for (expr, weight) in spec {
+        let hi = lo + weight;
+        out.push_str(&format!("        {lo}..{hi} => {expr},\n"));
+        lo = hi;
+    }
+    for v in 0..HASH_WEIGHT {
+        out.push_str(&format!("        {} => Instr::Op3(Op3::Hash({v})),\n", lo + v));
+    }
+    out
+}
+
+/// Render `spec` into `match` arms covering `0..16`, followed by a trailing
+/// `16..=255` arm so the match stays exhaustive over the full `u8` the
+/// caller matches on (the value is only ever a nibble in practice, enforced
+/// by the `assert!` right above the `include!`, but exhaustiveness is a
+/// static property independent of that runtime check). Panics (failing the
+/// build) if the weights don't tile exactly the 16 nibble values.
+fn generate_operand_match(spec: Spec) -> String {
+    let declared: u32 = spec.iter().map(|(_, w)| w).sum();
+    assert_eq!(declared, 16, "operand spec must tile exactly 16 nibble values, got {declared}");
+
+    let mut out = String::new();
+    let mut lo: u32 = 0;
+    for (expr, weight) in spec {
+        let hi = lo + weight;
+        out.push_str(&format!("        {lo}..{hi} => {expr},\n"));
+        lo = hi;
+    }
+    out.push_str(&format!(
+        "        {lo}..=255 => unreachable!(\"operand nibble masked to 0..=15, got {{value}}\"),\n"
+    ));
+    out
+}
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    fs::write(
+        Path::new(&out_dir).join("opcode_v1.rs"),
+        format!("match value {{\n{}    }}\n", generate_opcode_match(ISA_V1)),
+    )
+    .unwrap();
+    fs::write(
+        Path::new(&out_dir).join("opcode_v2.rs"),
+        format!("match value {{\n{}    }}\n", generate_opcode_match(ISA_V2)),
+    )
+    .unwrap();
+    fs::write(
+        Path::new(&out_dir).join("operand_table.rs"),
+        format!("match value {{\n{}    }}\n", generate_operand_match(OPERAND_SPEC)),
+    )
+    .unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
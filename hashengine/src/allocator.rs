@@ -0,0 +1,79 @@
+//! Central nonce-range allocator and worker registry.
+//!
+//! `/mine` and `/start-mining` used to derive a worker's nonce range from
+//! `worker_id * 1_000_000_000` and just keep incrementing from there
+//! forever -- a long-running `/start-mining` job eventually walks past its
+//! `1_000_000_000`-wide slice and starts duplicating whatever the next
+//! `worker_id` is doing. This hands out disjoint windows from one global
+//! high-water mark instead, leased on demand rather than assumed from a
+//! static base, and tracks enough about each worker to answer `GET
+//! /workers` the way a node's peers table would.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Size of each leased nonce window.
+pub const NONCE_WINDOW_SIZE: u64 = 1_000_000_000;
+
+static NEXT_NONCE: AtomicU64 = AtomicU64::new(0);
+
+/// Lease the next globally-unique `[start, start + NONCE_WINDOW_SIZE)`
+/// nonce window. Safe to call concurrently from any number of workers --
+/// `fetch_add` guarantees no two callers are ever handed overlapping
+/// windows, regardless of how long a previous lease has been running.
+pub fn lease_nonce_window() -> (u64, u64) {
+    let start = NEXT_NONCE.fetch_add(NONCE_WINDOW_SIZE, Ordering::Relaxed);
+    (start, start + NONCE_WINDOW_SIZE)
+}
+
+/// What the registry knows about one worker, refreshed on every
+/// `/mine`/`/start-mining` call (or background batch) it makes.
+#[derive(Debug, Clone)]
+pub struct WorkerInfo {
+    pub worker_id: u64,
+    pub address: String,
+    pub last_seen_unix_secs: u64,
+    pub hashrate: f64,
+    pub nonce_window_start: u64,
+    pub nonce_window_end: u64,
+}
+
+static WORKERS: once_cell::sync::Lazy<RwLock<HashMap<u64, WorkerInfo>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Record that `worker_id` (paying out to `address`) is working
+/// `[nonce_window_start, nonce_window_end)` at `hashrate` hashes/second,
+/// as of now.
+pub fn record_worker(
+    worker_id: u64,
+    address: String,
+    nonce_window_start: u64,
+    nonce_window_end: u64,
+    hashrate: f64,
+) {
+    WORKERS.write().unwrap().insert(
+        worker_id,
+        WorkerInfo {
+            worker_id,
+            address,
+            last_seen_unix_secs: unix_now_secs(),
+            hashrate,
+            nonce_window_start,
+            nonce_window_end,
+        },
+    );
+}
+
+/// Snapshot of every worker the registry has seen, ordered by `worker_id`,
+/// for `GET /workers`.
+pub fn snapshot_workers() -> Vec<WorkerInfo> {
+    let mut workers: Vec<_> = WORKERS.read().unwrap().values().cloned().collect();
+    workers.sort_by_key(|w| w.worker_id);
+    workers
+}
@@ -1,6 +1,8 @@
 use actix_web::{web, App, HttpResponse, HttpServer, middleware};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock, atomic::{AtomicU64, AtomicBool, Ordering}};
+use std::collections::HashMap;
+use std::thread;
 use rayon::prelude::*;
 use log::{info, error, warn, debug};
 use std::time::{Instant, Duration};
@@ -22,11 +24,34 @@ mod preimage {
 mod validation {
     include!("../validation.rs");
 }
+mod client {
+    include!("../client.rs");
+}
+mod stratum {
+    include!("../stratum.rs");
+}
+mod allocator {
+    include!("../allocator.rs");
+}
+mod rpc {
+    include!("../rpc.rs");
+}
+mod jobs {
+    include!("../jobs.rs");
+}
+mod metrics {
+    include!("../metrics.rs");
+}
+mod lifecycle {
+    include!("../lifecycle.rs");
+}
+mod timeouts {
+    include!("../timeouts.rs");
+}
 
 use hashengine::hash as sh_hash;
 use rom::{RomGenerationType, Rom};
 use preimage::{ChallengeData, build_preimage};
-use validation::matches_difficulty;
 
 // Global ROM state using RwLock to allow reinitialization for new challenges
 static ROM: once_cell::sync::Lazy<RwLock<Option<Arc<Rom>>>> = once_cell::sync::Lazy::new(|| RwLock::new(None));
@@ -46,6 +71,11 @@ struct InitRequest {
     no_pre_mine: String,
     #[serde(rename = "ashConfig")]
     ash_config: AshConfig,
+    /// Present when the caller wants this challenge advertised to connected
+    /// Stratum sockets. Optional for backwards compatibility with callers
+    /// that only ever drive mining over the per-request HTTP endpoints.
+    #[serde(default)]
+    challenge: Option<ChallengeData>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -114,16 +144,34 @@ struct MineRequest {
     address: String,
     challenge: ChallengeData,
     batch_size: usize,
-    nonce_start: String, // String to support large numbers from TypeScript
+    /// A decimal nonce to start from (string to support large numbers from
+    /// TypeScript), or `"auto"`/omitted to lease the next window from the
+    /// central [`allocator`].
+    #[serde(default)]
+    nonce_start: Option<String>,
+    /// Wall-clock budget for this call, in milliseconds. `mine_handler`
+    /// works through `batch_size` in sub-batches and checks this after each
+    /// one; if the deadline passes first, it returns whatever it has so far
+    /// with `exhausted: false` and a `next_nonce` cursor instead of blocking
+    /// (and risking a dropped connection) until the whole batch completes.
+    /// Omit for the old unbounded behavior.
+    #[serde(default)]
+    max_millis: Option<u64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct MineResponse {
     solutions: Vec<Solution>,
     hashes_computed: usize,
+    /// `false` if the run stopped before `batch_size` was reached -- a
+    /// solution was found, `max_millis` elapsed, or shutdown began -- resume
+    /// from `next_nonce`.
+    exhausted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_nonce: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct Solution {
     nonce: String,
     hash: String,
@@ -137,8 +185,57 @@ struct StartMiningRequest {
     worker_id: u64,
     address: String,
     challenge: ChallengeData,
+    /// A decimal nonce to start from, or `"auto"`/omitted to lease the next
+    /// window from the central [`allocator`] (and again each time this job
+    /// exhausts its current window).
+    #[serde(default)]
+    nonce_start: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct StartMiningResponse {
+    status: String,
+    worker_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct StopMiningRequest {
+    worker_id: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct StopMiningResponse {
+    status: String,
+    worker_id: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct MiningResultResponse {
+    status: String, // "running" | "found" | "cancelled"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solution: Option<Solution>,
+}
+
+/// Outcome of a background `/start-mining` run, written once by its worker
+/// thread and read by `/mining-result`.
+enum MiningJobResult {
+    Running,
+    Found(Solution),
+    Cancelled,
 }
 
+/// A `/start-mining` job's cancel flag and result slot. Looked up by
+/// `worker_id` in [`MINING_JOBS`]; `/stop-mining` flips `cancel`, the worker
+/// thread checks it once per batch, and `/mining-result` reads `result`.
+struct JobHandle {
+    cancel: Arc<AtomicBool>,
+    result: Arc<RwLock<MiningJobResult>>,
+}
+
+// Registry of in-flight background mining jobs, keyed by worker_id.
+static MINING_JOBS: once_cell::sync::Lazy<RwLock<HashMap<u64, JobHandle>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(HashMap::new()));
+
 #[derive(Debug, Serialize)]
 struct MiningStatsResponse {
     total_hashes: u64,
@@ -147,6 +244,13 @@ struct MiningStatsResponse {
     uptime_seconds: u64,
     mining_active: bool,
     cpu_mode: String,  // "max" or "normal"
+    jobs_awaiting_retry: usize,
+    total_job_retries: u64,
+    /// What [`validation::predicted_next_difficulty`] expects the next
+    /// challenge's difficulty to be, from recent submission history -- so a
+    /// caller can pre-size its next batch, or skip a challenge that isn't
+    /// worth attempting, before spending any hashes on it.
+    predicted_next_difficulty: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -199,6 +303,10 @@ async fn init_handler(req: web::Json<InitRequest>) -> HttpResponse {
 
     info!("✓ ROM initialized in {:.1}s", elapsed);
 
+    if let Some(challenge) = req.challenge.clone() {
+        stratum::publish_new_challenge(challenge).await;
+    }
+
     HttpResponse::Ok().json(InitResponse {
         status: "initialized".to_string(),
         worker_pid: std::process::id(),
@@ -359,62 +467,42 @@ async fn health_handler() -> HttpResponse {
     })
 }
 
-/// GET /stats - Get mining statistics and hash rate
-async fn stats_handler() -> HttpResponse {
-    // Check if we need to reset hourly counters (prevent overflow)
-    let mut reset_lock = LAST_RESET_TIME.write().unwrap();
-    let now = Instant::now();
-
-    let should_reset = if let Some(last_reset) = *reset_lock {
-        // Reset every hour
-        last_reset.elapsed() >= Duration::from_secs(3600)
-    } else {
-        // First time - initialize
-        true
-    };
+#[derive(Debug, Serialize)]
+struct ReadinessResponse {
+    status: String,
+    workers_active: usize,
+    queue_len: usize,
+}
 
-    if should_reset {
-        info!("Resetting hourly hash counter (prevents overflow)");
-        TOTAL_HASHES.store(0, Ordering::Relaxed);
-        *reset_lock = Some(now);
-    }
+/// GET /health/live - Liveness probe: 200 iff the actix event loop is
+/// responding at all. No readiness logic lives here -- an orchestrator uses
+/// this to decide whether to restart the process, not whether to route
+/// traffic to it.
+async fn liveness_handler() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "status": "alive" }))
+}
 
-    let total_hashes = TOTAL_HASHES.load(Ordering::Relaxed);
-    let solutions_found = SOLUTIONS_FOUND.load(Ordering::Relaxed);
-    let mining_active = MINING_ACTIVE.load(Ordering::Relaxed);
-
-    let hash_rate = if let Some(reset_time) = *reset_lock {
-        let elapsed = reset_time.elapsed().as_secs();
-        let rate = if elapsed > 0 {
-            total_hashes / elapsed
-        } else {
-            0
-        };
-        rate
-    } else {
-        0
+/// GET /health/ready - Readiness probe: 503 once the mining worker pool is
+/// fully saturated or the job queue has backed past its high-water mark, so
+/// a load balancer can route around a node that's already maxed on
+/// long-running mining jobs instead of piling more onto it.
+async fn readiness_handler() -> HttpResponse {
+    let pool = jobs::pool_status();
+    let body = ReadinessResponse {
+        status: if pool.saturated { "saturated".to_string() } else { "available".to_string() },
+        workers_active: pool.workers_active,
+        queue_len: pool.queue_len,
     };
-    drop(reset_lock);
-
-    let stats_lock = STATS_START_TIME.read().unwrap();
-    let uptime_seconds = if let Some(start_time) = *stats_lock {
-        let elapsed = start_time.elapsed().as_secs();
-        elapsed
+    if pool.saturated {
+        HttpResponse::ServiceUnavailable().json(body)
     } else {
-        0
-    };
-    drop(stats_lock);
-
-    let cpu_mode = CPU_MODE.read().unwrap().clone();
+        HttpResponse::Ok().json(body)
+    }
+}
 
-    HttpResponse::Ok().json(MiningStatsResponse {
-        total_hashes,
-        solutions_found,
-        hash_rate,
-        uptime_seconds,
-        mining_active,
-        cpu_mode,
-    })
+/// GET /stats - Get mining statistics and hash rate
+async fn stats_handler() -> HttpResponse {
+    HttpResponse::Ok().json(rpc::compute_stats())
 }
 
 /// POST /set-cpu-mode - Set CPU usage mode (max or normal)
@@ -458,102 +546,95 @@ async fn set_cpu_mode_handler(req: web::Json<SetCpuModeRequest>) -> HttpResponse
 
 /// POST /start-mining - Start continuous mining (long-running endpoint)
 /// This endpoint mines continuously until a solution is found or an error occurs
-async fn start_mining_handler(req: web::Json<StartMiningRequest>) -> HttpResponse {
-    info!("POST /start-mining: Worker {} starting for address {}", req.worker_id, req.address);
-
-    // Get ROM
-    let rom_lock = ROM.read().unwrap();
-    let rom = match rom_lock.as_ref() {
-        Some(r) => Arc::clone(r),
-        None => {
-            error!("ROM not initialized");
-            return HttpResponse::ServiceUnavailable().json(ErrorResponse {
-                error: "ROM not initialized. Call /init first.".to_string(),
-            });
-        }
-    };
-    drop(rom_lock);
-
-    // Initialize stats if this is the first mining request
-    {
-        let mut stats_lock = STATS_START_TIME.write().unwrap();
-        if stats_lock.is_none() {
-            *stats_lock = Some(Instant::now());
-        }
-    }
-
-    MINING_ACTIVE.store(true, Ordering::Relaxed);
-
+/// The `/start-mining` search loop, run on a dedicated OS thread so it never
+/// ties up an actix worker. Checks `cancel` once per batch and writes its
+/// outcome to `result` before returning, for `/stop-mining` and
+/// `/mining-result` to observe.
+fn run_mining_job(
+    worker_id: u64,
+    address: String,
+    challenge: ChallengeData,
+    rom: Arc<Rom>,
+    cancel: Arc<AtomicBool>,
+    result: Arc<RwLock<MiningJobResult>>,
+    initial_nonce_start: Option<u64>,
+) {
     let start_time = Instant::now();
-    let mut nonce_counter: u64 = (req.worker_id * 1_000_000_000); // Worker-specific nonce range
+    let mut window = initial_nonce_start
+        .map(|start| (start, start + allocator::NONCE_WINDOW_SIZE))
+        .unwrap_or_else(allocator::lease_nonce_window);
+    let mut nonce_counter: u64 = window.0;
+    let mut last_batch_at = Instant::now();
     const BATCH_SIZE: usize = 10000; // Optimized batch size (4 workers × 10K = 40K total parallel hashing)
 
     loop {
-        // Generate batch of nonces and preimages
-        let batch_data: Vec<(String, String)> = (0..BATCH_SIZE)
-            .map(|i| {
-                let nonce_num = nonce_counter + i as u64;
-                let nonce_hex = format!("{:016x}", nonce_num);
-                let preimage = build_preimage(&nonce_hex, &req.address, &req.challenge);
-                (nonce_hex, preimage)
-            })
-            .collect();
+        if cancel.load(Ordering::Relaxed) || lifecycle::is_shutting_down() {
+            info!("Worker {worker_id}: mining job cancelled at nonce {nonce_counter}");
+            *result.write().unwrap() = MiningJobResult::Cancelled;
+            return;
+        }
 
+        // This window is exhausted -- lease the next one rather than
+        // walking past it into whatever range another worker was leased.
+        if nonce_counter >= window.1 {
+            window = allocator::lease_nonce_window();
+            nonce_counter = window.0;
+        }
+
+        let batch_nonce_start = nonce_counter;
         nonce_counter += BATCH_SIZE as u64;
 
-        // Parallel hash computation with inline validation
-        let found_solution: Option<Solution> = batch_data
+        // Split the batch into chunks and scan each one via
+        // hashengine::mine_batch, in parallel across rayon's thread pool --
+        // this reuses one preimage buffer per chunk instead of formatting a
+        // fresh nonce/preimage String pair up front for every single nonce.
+        const CHUNK_SIZE: usize = 256;
+        let found_solution: Option<Solution> = (0..BATCH_SIZE)
+            .step_by(CHUNK_SIZE)
+            .collect::<Vec<usize>>()
             .par_iter()
-            .find_map_any(|(nonce, preimage)| {
-                let salt = preimage.as_bytes();
-                let hash_bytes = sh_hash(salt, &rom, 8, 256);
-                let hash_hex = hex::encode(hash_bytes);
-
-                // Inline difficulty check (dual validation)
-                match matches_difficulty(&hash_hex, &req.challenge.difficulty) {
-                    Ok(true) => {
-                        info!(
-                            "Worker {} found solution! Nonce: {}, Hash: {}...",
-                            req.worker_id,
-                            nonce,
-                            &hash_hex[..16]
-                        );
-                        Some(Solution {
-                            nonce: nonce.clone(),
-                            hash: hash_hex,
-                            preimage: preimage.clone(),
-                        })
-                    }
-                    Ok(false) => None,
-                    Err(e) => {
-                        warn!("Validation error for nonce {}: {}", nonce, e);
-                        None
-                    }
-                }
+            .find_map_any(|&offset| {
+                let chunk_len = CHUNK_SIZE.min(BATCH_SIZE - offset);
+                let chunk_base = batch_nonce_start + offset as u64;
+                let found_nonce = hashengine::mine_batch(chunk_base, chunk_len, &rom, &address, &challenge)?;
+
+                let nonce_hex = format!("{:016x}", found_nonce);
+                let preimage = build_preimage(&nonce_hex, &address, &challenge);
+                let hash_hex = hex::encode(sh_hash(preimage.as_bytes(), &rom, 8, 256));
+                info!(
+                    "Worker {} found solution! Nonce: {}, Hash: {}...",
+                    worker_id,
+                    nonce_hex,
+                    &hash_hex[..16]
+                );
+                Some(Solution { nonce: nonce_hex, hash: hash_hex, preimage })
             });
 
         // Update global stats
         TOTAL_HASHES.fetch_add(BATCH_SIZE as u64, Ordering::Relaxed);
 
-        // If solution found, return it
+        let batch_hash_rate = BATCH_SIZE as f64 / last_batch_at.elapsed().as_secs_f64().max(f64::EPSILON);
+        last_batch_at = Instant::now();
+        allocator::record_worker(worker_id, address.clone(), window.0, window.1, batch_hash_rate);
+
+        // If solution found, record it and stop
         if let Some(solution) = found_solution {
             SOLUTIONS_FOUND.fetch_add(1, Ordering::Relaxed);
+            validation::record_submission(challenge.clone());
 
             let elapsed = start_time.elapsed();
             let hash_rate = (nonce_counter as f64 / elapsed.as_secs_f64()) as u64;
 
             info!(
                 "Worker {}: Found solution after {} hashes in {:.2}s ({} H/s)",
-                req.worker_id,
+                worker_id,
                 nonce_counter,
                 elapsed.as_secs_f64(),
                 hash_rate
             );
 
-            return HttpResponse::Ok().json(MineResponse {
-                solutions: vec![solution],
-                hashes_computed: nonce_counter as usize,
-            });
+            *result.write().unwrap() = MiningJobResult::Found(solution);
+            return;
         }
 
         // Log progress every million hashes
@@ -562,7 +643,7 @@ async fn start_mining_handler(req: web::Json<StartMiningRequest>) -> HttpRespons
             let hash_rate = (nonce_counter as f64 / elapsed.as_secs_f64()) as u64;
             debug!(
                 "Worker {}: {} hashes in {:.2}s ({} H/s)",
-                req.worker_id,
+                worker_id,
                 nonce_counter,
                 elapsed.as_secs_f64(),
                 hash_rate
@@ -571,10 +652,16 @@ async fn start_mining_handler(req: web::Json<StartMiningRequest>) -> HttpRespons
     }
 }
 
-/// POST /mine - Autonomous mining endpoint
-/// Generates preimages internally, hashes them, validates difficulty, and returns only solutions
-async fn mine_handler(req: web::Json<MineRequest>) -> HttpResponse {
-    let start_time = std::time::Instant::now();
+/// POST /start-mining - Spawn a background mining job for `worker_id` and
+/// return immediately; poll `/mining-result/{worker_id}` for the outcome.
+async fn start_mining_handler(req: web::Json<StartMiningRequest>) -> HttpResponse {
+    info!("POST /start-mining: Worker {} starting for address {}", req.worker_id, req.address);
+
+    if lifecycle::is_shutting_down() {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "server is shutting down; not accepting new mining jobs".to_string(),
+        });
+    }
 
     // Get ROM
     let rom_lock = ROM.read().unwrap();
@@ -589,87 +676,318 @@ async fn mine_handler(req: web::Json<MineRequest>) -> HttpResponse {
     };
     drop(rom_lock);
 
-    // Parse starting nonce from string
-    let nonce_start = match req.nonce_start.parse::<u64>() {
-        Ok(n) => n,
-        Err(e) => {
-            return HttpResponse::BadRequest().json(ErrorResponse {
-                error: format!("Invalid nonce_start: {}", e),
+    // Initialize stats if this is the first mining request
+    {
+        let mut stats_lock = STATS_START_TIME.write().unwrap();
+        if stats_lock.is_none() {
+            *stats_lock = Some(Instant::now());
+        }
+    }
+
+    let initial_nonce_start = match req.nonce_start.as_deref() {
+        None | Some("auto") => None,
+        Some(explicit) => match explicit.parse::<u64>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: format!("Invalid nonce_start: {}", e),
+                });
+            }
+        },
+    };
+
+    MINING_ACTIVE.store(true, Ordering::Relaxed);
+
+    // A worker restarting mid-run replaces its job; cancel whatever was
+    // already there first so it doesn't keep burning a thread.
+    if let Some(previous) = MINING_JOBS.write().unwrap().remove(&req.worker_id) {
+        previous.cancel.store(true, Ordering::Relaxed);
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let result = Arc::new(RwLock::new(MiningJobResult::Running));
+
+    {
+        let worker_id = req.worker_id;
+        let address = req.address.clone();
+        let challenge = req.challenge.clone();
+        let rom = Arc::clone(&rom);
+        let cancel = Arc::clone(&cancel);
+        let result = Arc::clone(&result);
+        thread::spawn(move || {
+            run_mining_job(worker_id, address, challenge, rom, cancel, result, initial_nonce_start)
+        });
+    }
+
+    MINING_JOBS.write().unwrap().insert(req.worker_id, JobHandle { cancel, result });
+
+    HttpResponse::Ok().json(StartMiningResponse {
+        status: "started".to_string(),
+        worker_id: req.worker_id,
+    })
+}
+
+/// POST /stop-mining - Flip the cancel flag for a background mining job.
+/// The worker thread exits (and records `Cancelled`) at its next batch
+/// boundary; this returns immediately rather than waiting for that.
+async fn stop_mining_handler(req: web::Json<StopMiningRequest>) -> HttpResponse {
+    let jobs = MINING_JOBS.read().unwrap();
+    match jobs.get(&req.worker_id) {
+        Some(job) => {
+            job.cancel.store(true, Ordering::Relaxed);
+            info!("POST /stop-mining: Worker {} stop requested", req.worker_id);
+            HttpResponse::Ok().json(StopMiningResponse {
+                status: "stopping".to_string(),
+                worker_id: req.worker_id,
+            })
+        }
+        None => HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("no mining job found for worker {}", req.worker_id),
+        }),
+    }
+}
+
+/// GET /metrics - Prometheus text-exposition metrics for the job queue.
+async fn metrics_handler() -> HttpResponse {
+    HttpResponse::Ok().content_type("text/plain; version=0.0.4").body(metrics::render())
+}
+
+/// GET /mining-result/{worker_id} - Fetch a background mining job's current
+/// status: still running, a found solution, or cancelled.
+async fn mining_result_handler(path: web::Path<u64>) -> HttpResponse {
+    let worker_id = path.into_inner();
+    let jobs = MINING_JOBS.read().unwrap();
+    let job = match jobs.get(&worker_id) {
+        Some(job) => job,
+        None => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: format!("no mining job found for worker {worker_id}"),
             });
         }
     };
 
-    // Generate batch of nonces and preimages
-    let batch_data: Vec<(String, String)> = (0..req.batch_size)
-        .map(|i| {
-            let nonce_num = nonce_start + i as u64;
-            let nonce_hex = format!("{:016x}", nonce_num);
-            let preimage = build_preimage(&nonce_hex, &req.address, &req.challenge);
-            (nonce_hex, preimage)
-        })
-        .collect();
+    let response = match &*job.result.read().unwrap() {
+        MiningJobResult::Running => MiningResultResponse { status: "running".to_string(), solution: None },
+        MiningJobResult::Found(solution) => {
+            MiningResultResponse { status: "found".to_string(), solution: Some(solution.clone()) }
+        }
+        MiningJobResult::Cancelled => MiningResultResponse { status: "cancelled".to_string(), solution: None },
+    };
+    HttpResponse::Ok().json(response)
+}
 
-    // Parallel hash computation with inline validation
-    let found_solutions: Vec<Solution> = batch_data
-        .par_iter()
-        .filter_map(|(nonce, preimage)| {
-            let salt = preimage.as_bytes();
-            let hash_bytes = sh_hash(salt, &rom, 8, 256);
-            let hash_hex = hex::encode(hash_bytes);
-
-            // Inline difficulty check (dual validation)
-            match matches_difficulty(&hash_hex, &req.challenge.difficulty) {
-                Ok(true) => {
-                    info!(
-                        "Worker {} found solution! Nonce: {}, Hash: {}...",
-                        req.worker_id,
-                        nonce,
-                        &hash_hex[..16]
-                    );
-                    Some(Solution {
-                        nonce: nonce.clone(),
-                        hash: hash_hex,
-                        preimage: preimage.clone(),
-                    })
-                }
-                Ok(false) => None,
-                Err(e) => {
-                    warn!("Validation error for nonce {}: {}", nonce, e);
-                    None
-                }
-            }
-        })
-        .collect();
+#[derive(Debug, Serialize)]
+struct WorkerView {
+    worker_id: u64,
+    address: String,
+    last_seen_unix_secs: u64,
+    hashrate: f64,
+    nonce_window_start: String,
+    nonce_window_end: String,
+}
+
+impl From<allocator::WorkerInfo> for WorkerView {
+    fn from(w: allocator::WorkerInfo) -> Self {
+        Self {
+            worker_id: w.worker_id,
+            address: w.address,
+            last_seen_unix_secs: w.last_seen_unix_secs,
+            hashrate: w.hashrate,
+            nonce_window_start: w.nonce_window_start.to_string(),
+            nonce_window_end: w.nonce_window_end.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct WorkersResponse {
+    workers: Vec<WorkerView>,
+}
+
+/// GET /workers - List every worker the allocator has seen: its payout
+/// address, last-seen time, observed hashrate, and currently-leased nonce
+/// window. The connected-peers table equivalent for this pool.
+async fn workers_handler() -> HttpResponse {
+    HttpResponse::Ok().json(WorkersResponse {
+        workers: allocator::snapshot_workers().into_iter().map(WorkerView::from).collect(),
+    })
+}
+
+/// Runs a whole `/mine` request to completion: resolves the ROM and starting
+/// nonce, works through `batch_size` in sub-batches (respecting `max_millis`
+/// and stopping early on a solution), and records the worker in the
+/// [`allocator`] registry. Shared by `mine_handler` and the [`jobs`] worker
+/// pool so both surfaces run the exact same mining logic.
+///
+/// `cancel` is checked at the same sub-batch checkpoints as `max_millis`: a
+/// process-wide shutdown aborts an in-flight run exactly like a deadline
+/// would, returning a resumable partial result (`exhausted: false` plus
+/// `next_nonce`) instead of being hard-killed mid-batch.
+fn execute_mine(
+    req: &MineRequest,
+    cancel: &tokio_util::sync::CancellationToken,
+) -> std::result::Result<MineResponse, String> {
+    let start_time = std::time::Instant::now();
+
+    // Get ROM
+    let rom_lock = ROM.read().unwrap();
+    let rom = match rom_lock.as_ref() {
+        Some(r) => Arc::clone(r),
+        None => {
+            error!("ROM not initialized");
+            return Err("ROM not initialized. Call /init first.".to_string());
+        }
+    };
+    drop(rom_lock);
+
+    // Parse starting nonce from string, or lease the next free window from
+    // the central allocator if the caller asked for "auto" (or left it out).
+    let nonce_start = match req.nonce_start.as_deref() {
+        None | Some("auto") => allocator::lease_nonce_window().0,
+        Some(explicit) => explicit.parse::<u64>().map_err(|e| format!("Invalid nonce_start: {}", e))?,
+    };
+
+    // Work through batch_size in sub-batches rather than all at once, so a
+    // large batch_size can't block the connection past max_millis (if the
+    // caller set one) -- and so a solution found partway through returns
+    // immediately instead of waiting for the rest of the batch.
+    const SUB_BATCH_SIZE: usize = 10_000;
+    let deadline = req.max_millis.map(|ms| start_time + Duration::from_millis(ms));
+
+    let mut found_solutions: Vec<Solution> = Vec::new();
+    let mut hashes_computed: usize = 0;
+    let mut exhausted = false;
+
+    while hashes_computed < req.batch_size {
+        let this_batch = (req.batch_size - hashes_computed).min(SUB_BATCH_SIZE);
+        let batch_nonce_start = nonce_start + hashes_computed as u64;
+
+        // Split the sub-batch into chunks and scan each one via
+        // hashengine::mine_batch, in parallel across rayon's thread pool --
+        // this reuses one preimage buffer per chunk instead of formatting a
+        // fresh nonce/preimage String pair up front for every single nonce.
+        const CHUNK_SIZE: usize = 256;
+        let sub_solutions: Vec<Solution> = (0..this_batch)
+            .step_by(CHUNK_SIZE)
+            .collect::<Vec<usize>>()
+            .par_iter()
+            .filter_map(|&offset| {
+                let chunk_len = CHUNK_SIZE.min(this_batch - offset);
+                let chunk_base = batch_nonce_start + offset as u64;
+                let found_nonce = hashengine::mine_batch(chunk_base, chunk_len, &rom, &req.address, &req.challenge)?;
+
+                let nonce_hex = format!("{:016x}", found_nonce);
+                let preimage = build_preimage(&nonce_hex, &req.address, &req.challenge);
+                let hash_hex = hex::encode(sh_hash(preimage.as_bytes(), &rom, 8, 256));
+                info!(
+                    "Worker {} found solution! Nonce: {}, Hash: {}...",
+                    req.worker_id,
+                    nonce_hex,
+                    &hash_hex[..16]
+                );
+                Some(Solution { nonce: nonce_hex, hash: hash_hex, preimage })
+            })
+            .collect();
+
+        hashes_computed += this_batch;
+        let found_any = !sub_solutions.is_empty();
+        if found_any {
+            validation::record_submission(req.challenge.clone());
+        }
+        found_solutions.extend(sub_solutions);
+
+        if found_any || hashes_computed >= req.batch_size {
+            exhausted = !found_any;
+            break;
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) || cancel.is_cancelled() {
+            break;
+        }
+    }
+
+    let next_nonce =
+        if exhausted { None } else { Some((nonce_start + hashes_computed as u64).to_string()) };
 
     let elapsed = start_time.elapsed();
-    let hash_rate = (req.batch_size as f64 / elapsed.as_secs_f64()) as u64;
+    let hash_rate = (hashes_computed as f64 / elapsed.as_secs_f64()) as u64;
+
+    allocator::record_worker(
+        req.worker_id,
+        req.address.clone(),
+        nonce_start,
+        nonce_start + hashes_computed as u64,
+        hash_rate as f64,
+    );
 
     // Log performance (only when solutions found or at debug level)
     if !found_solutions.is_empty() {
         info!(
             "Worker {}: {} hashes in {:.2}ms ({} H/s) - {} solutions found",
             req.worker_id,
-            req.batch_size,
+            hashes_computed,
             elapsed.as_secs_f64() * 1000.0,
             hash_rate,
             found_solutions.len()
         );
     } else {
         debug!(
-            "Worker {}: {} hashes in {:.2}ms ({} H/s) - no solutions",
+            "Worker {}: {} hashes in {:.2}ms ({} H/s) - no solutions{}",
             req.worker_id,
-            req.batch_size,
+            hashes_computed,
             elapsed.as_secs_f64() * 1000.0,
-            hash_rate
+            hash_rate,
+            if exhausted { "" } else { " (deadline hit, resumable)" }
         );
     }
 
-    HttpResponse::Ok().json(MineResponse {
+    Ok(MineResponse {
         solutions: found_solutions,
-        hashes_computed: req.batch_size,
+        hashes_computed,
+        exhausted,
+        next_nonce,
     })
 }
 
+/// POST /mine - Autonomous mining endpoint
+/// Generates preimages internally, hashes them, validates difficulty, and returns only solutions
+async fn mine_handler(req: web::Json<MineRequest>) -> HttpResponse {
+    match execute_mine(&req, &lifecycle::token()) {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) if e.starts_with("Invalid nonce_start") => {
+            HttpResponse::BadRequest().json(ErrorResponse { error: e })
+        }
+        Err(e) => HttpResponse::ServiceUnavailable().json(ErrorResponse { error: e }),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JobSubmitResponse {
+    job_id: u64,
+}
+
+/// POST /jobs - Enqueue a `/mine`-shaped request onto the [`jobs`] worker
+/// pool and return its id immediately, instead of holding the connection
+/// open for the duration of the mining run.
+async fn jobs_submit_handler(req: web::Json<MineRequest>) -> HttpResponse {
+    match jobs::submit(req.into_inner()) {
+        Some(job_id) => HttpResponse::Ok().json(JobSubmitResponse { job_id }),
+        None => HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "server is shutting down; not accepting new jobs".to_string(),
+        }),
+    }
+}
+
+/// GET /jobs/{id} - Poll a submitted job's status
+/// (queued/running/done/failed) and result.
+async fn jobs_get_handler(path: web::Path<u64>) -> HttpResponse {
+    match jobs::get(path.into_inner()) {
+        Some(view) => HttpResponse::Ok().json(view),
+        None => HttpResponse::NotFound().json(ErrorResponse {
+            error: "no job found for that id".to_string(),
+        }),
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // Initialize logger
@@ -724,7 +1042,28 @@ async fn main() -> std::io::Result<()> {
     info!("Config: hash-config.json (edit cpu_percentage to change)");
     info!("═══════════════════════════════════════════════════════════");
 
-    HttpServer::new(|| {
+    let stratum_host = std::env::var("STRATUM_HOST").unwrap_or_else(|_| host.clone());
+    let stratum_port = std::env::var("STRATUM_PORT").unwrap_or_else(|_| "3333".to_string());
+    tokio::spawn(async move {
+        if let Err(e) = stratum::run(format!("{}:{}", stratum_host, stratum_port)).await {
+            error!("Stratum server exited: {e}");
+        }
+    });
+
+    // Long mining runs now go through POST /jobs (queued, polled via GET
+    // /jobs/{id}) or /mine's own max_millis deadline rather than holding a
+    // connection open -- no more need for the old 24-hour keep-alive, which
+    // tied up an actix worker per in-flight request and broke behind any
+    // proxy with a shorter timeout of its own.
+    jobs::init(workers);
+
+    // Control routes (`/stats`, `/set-cpu-mode`, `/health*`) get their own
+    // short `ControlTimeout`, independent of the server-wide budget below
+    // (which has to stay sized for the mining path) -- a slow or malicious
+    // control-plane client can no longer hold a worker for that long.
+    let timeout_config = timeouts::TimeoutConfig::from_env();
+
+    let server = HttpServer::new(move || {
         App::new()
             // Logger middleware removed - only log important events via RUST_LOG
             .route("/init", web::post().to(init_handler))
@@ -732,15 +1071,68 @@ async fn main() -> std::io::Result<()> {
             .route("/hash-batch", web::post().to(hash_batch_handler))
             .route("/hash-batch-shared", web::post().to(hash_batch_shared_handler))
             .route("/mine", web::post().to(mine_handler))
+            .route("/jobs", web::post().to(jobs_submit_handler))
+            .route("/jobs/{id}", web::get().to(jobs_get_handler))
             .route("/start-mining", web::post().to(start_mining_handler))
-            .route("/stats", web::get().to(stats_handler))
-            .route("/set-cpu-mode", web::post().to(set_cpu_mode_handler))
-            .route("/health", web::get().to(health_handler))
+            .route("/stop-mining", web::post().to(stop_mining_handler))
+            .route("/mining-result/{worker_id}", web::get().to(mining_result_handler))
+            .route("/workers", web::get().to(workers_handler))
+            .route("/rpc", web::post().to(rpc::rpc_handler))
+            .service(
+                web::scope("")
+                    .wrap(timeouts::ControlTimeout::new(timeout_config.control_request_timeout))
+                    .route("/stats", web::get().to(stats_handler))
+                    .route("/set-cpu-mode", web::post().to(set_cpu_mode_handler))
+                    .route("/health", web::get().to(health_handler))
+                    .route("/health/live", web::get().to(liveness_handler))
+                    .route("/health/ready", web::get().to(readiness_handler))
+                    .route("/metrics", web::get().to(metrics_handler)),
+            )
     })
     .workers(workers)
-    .keep_alive(Duration::from_secs(3600 * 24)) // 24 hour keep-alive for long-running mining requests
-    .client_request_timeout(Duration::from_secs(3600 * 24)) // 24 hour timeout
+    .keep_alive(timeout_config.connect_timeout)
+    .client_request_timeout(timeout_config.mining_job_timeout)
     .bind(format!("{}:{}", host, port))?
-    .run()
-    .await
+    .run();
+
+    // On SIGINT/SIGTERM: cancel every background mining job, give them a
+    // moment to drain, log final stats, then let HttpServer finish its own
+    // graceful stop -- so the process never leaves orphaned mining threads
+    // burning CPU after it exits.
+    let server_handle = server.handle();
+    tokio::spawn(async move {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => info!("Received SIGINT, shutting down..."),
+            _ = sigterm.recv() => info!("Received SIGTERM, shutting down..."),
+        }
+
+        // Cancel the shared token first -- every in-flight /mine, /jobs, and
+        // /start-mining run notices it at its own next checkpoint and
+        // returns/exits instead of running to completion (or being hard-killed).
+        lifecycle::begin_shutdown();
+
+        MINING_ACTIVE.store(false, Ordering::Relaxed);
+        for job in MINING_JOBS.read().unwrap().values() {
+            job.cancel.store(true, Ordering::Relaxed);
+        }
+
+        let drained = jobs::drain_queue();
+        if drained > 0 {
+            info!("Drained {drained} queued job(s) that hadn't started yet");
+        }
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        info!(
+            "Final stats: {} hashes computed, {} solutions found",
+            TOTAL_HASHES.load(Ordering::Relaxed),
+            SOLUTIONS_FOUND.load(Ordering::Relaxed),
+        );
+
+        server_handle.stop(true).await;
+    });
+
+    server.await
 }
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::rom::Rom;
+use crate::hashengine::ChallengeParams;
+
+/// Result of submitting a mined solution to the upstream challenge service.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmitOutcome {
+    Accepted,
+    Rejected(String),
+}
+
+/// The parts of a challenge that come from the network. Deliberately leaves
+/// out `rom: Arc<Rom>` (the already-built ROM), since rebuilding the ROM is
+/// a separate, expensive step that a challenge-refresh poll should not repeat
+/// unless `rom_key` itself changed.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChallengeInfo {
+    pub rom_key: String,
+    pub difficulty_mask: String,
+    pub address: String,
+    pub challenge_id: String,
+    pub latest_submission: String,
+    pub no_pre_mine_hour: String,
+    pub required_zero_bits: usize,
+}
+
+impl ChallengeInfo {
+    /// Combine this challenge info with an already-built ROM into the full
+    /// [`ChallengeParams`] a `spin` worker needs.
+    pub fn into_params(self, rom: Arc<Rom>) -> ChallengeParams {
+        ChallengeParams {
+            rom_key: self.rom_key,
+            difficulty_mask: self.difficulty_mask,
+            address: self.address,
+            challenge_id: self.challenge_id,
+            latest_submission: self.latest_submission,
+            no_pre_mine_hour: self.no_pre_mine_hour,
+            required_zero_bits: self.required_zero_bits,
+            rom,
+        }
+    }
+
+    /// Whether this challenge supersedes the one workers are currently
+    /// grinding against. Compares the same fields the API uses to identify a
+    /// challenge, not the derived `required_zero_bits`/`rom_key`.
+    pub fn is_fresher_than(&self, current: &ChallengeParams) -> bool {
+        self.challenge_id != current.challenge_id
+            || self.latest_submission != current.latest_submission
+            || self.no_pre_mine_hour != current.no_pre_mine_hour
+    }
+}
+
+/// Talks to the challenge service from a blocking context (a `spin` worker
+/// thread or a synchronous CLI). Mirrors [`AsyncClient`] method for method;
+/// the orchestration logic in `hashengine::scavenge` runs against this
+/// blocking trait only.
+pub trait SyncClient {
+    fn fetch_challenge(&self) -> Result<ChallengeInfo, String>;
+    fn submit_solution(&self, nonce: u64, preimage: &str) -> Result<SubmitOutcome, String>;
+}
+
+/// Tokio-based counterpart to [`SyncClient`], for callers already running on
+/// an async runtime (e.g. the actix HTTP server).
+pub trait AsyncClient {
+    fn fetch_challenge(&self) -> impl std::future::Future<Output = Result<ChallengeInfo, String>> + Send;
+    fn submit_solution(&self, nonce: u64, preimage: &str) -> impl std::future::Future<Output = Result<SubmitOutcome, String>> + Send;
+}
+
+/// Blocking HTTP implementation of [`SyncClient`] against a challenge
+/// service exposing `GET {base_url}/challenge` and `POST {base_url}/submit`.
+pub struct HttpSyncClient {
+    base_url: String,
+    agent: reqwest::blocking::Client,
+}
+
+impl HttpSyncClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            agent: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl SyncClient for HttpSyncClient {
+    fn fetch_challenge(&self) -> Result<ChallengeInfo, String> {
+        self.agent
+            .get(format!("{}/challenge", self.base_url))
+            .send()
+            .map_err(|e| format!("fetch_challenge request failed: {e}"))?
+            .json::<ChallengeInfo>()
+            .map_err(|e| format!("fetch_challenge response was not valid JSON: {e}"))
+    }
+
+    fn submit_solution(&self, nonce: u64, preimage: &str) -> Result<SubmitOutcome, String> {
+        let body = serde_json::json!({ "nonce": format!("{:016x}", nonce), "preimage": preimage });
+        let response = self
+            .agent
+            .post(format!("{}/submit", self.base_url))
+            .json(&body)
+            .send()
+            .map_err(|e| format!("submit_solution request failed: {e}"))?;
+
+        if response.status().is_success() {
+            Ok(SubmitOutcome::Accepted)
+        } else {
+            let reason = response.text().unwrap_or_else(|_| "unknown error".to_string());
+            Ok(SubmitOutcome::Rejected(reason))
+        }
+    }
+}
+
+/// Async HTTP implementation of [`AsyncClient`], identical in shape to
+/// [`HttpSyncClient`] but built on `reqwest`'s tokio-based client.
+pub struct HttpAsyncClient {
+    base_url: String,
+    agent: reqwest::Client,
+}
+
+impl HttpAsyncClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            agent: reqwest::Client::new(),
+        }
+    }
+}
+
+impl AsyncClient for HttpAsyncClient {
+    async fn fetch_challenge(&self) -> Result<ChallengeInfo, String> {
+        self.agent
+            .get(format!("{}/challenge", self.base_url))
+            .send()
+            .await
+            .map_err(|e| format!("fetch_challenge request failed: {e}"))?
+            .json::<ChallengeInfo>()
+            .await
+            .map_err(|e| format!("fetch_challenge response was not valid JSON: {e}"))
+    }
+
+    async fn submit_solution(&self, nonce: u64, preimage: &str) -> Result<SubmitOutcome, String> {
+        let body = serde_json::json!({ "nonce": format!("{:016x}", nonce), "preimage": preimage });
+        let response = self
+            .agent
+            .post(format!("{}/submit", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("submit_solution request failed: {e}"))?;
+
+        if response.status().is_success() {
+            Ok(SubmitOutcome::Accepted)
+        } else {
+            let reason = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            Ok(SubmitOutcome::Rejected(reason))
+        }
+    }
+}
+
+/// Exponential backoff with a one-minute ceiling, used between retried
+/// `submit_solution` attempts so a flaky upstream doesn't get hammered.
+pub fn retry_backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt).min(60))
+}
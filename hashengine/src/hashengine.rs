@@ -9,11 +9,14 @@ use cryptoxide::{
 use blake2b_simd::{Params, State};
 
 // ** Consolidated Imports required for scavenge function **
-use std::sync::mpsc::{Sender, channel};
+use std::sync::mpsc::{Sender, RecvTimeoutError, channel};
 use std::{sync::Arc, thread, time::SystemTime};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use crate::client::{ChallengeInfo, SubmitOutcome, SyncClient, retry_backoff};
 // use indicatif::{ProgressBar, ProgressStyle};
 use hex;
+use std::fmt;
 // ************************************
 
 
@@ -38,6 +41,36 @@ struct VM {
     prog_seed: [u8; 64],
     memory_counter: u32,
     loop_counter: u32,
+    trace: Option<Vec<TraceEntry>>,
+    isa: IsaGeneration,
+}
+
+/// One executed instruction as recorded by a traced `VM`.
+///
+/// Carries the decoded instruction alongside the register inputs it read
+/// and the value it wrote back, so a whole run can be replayed instruction
+/// by instruction against the reference implementation.
+#[derive(Clone)]
+pub struct TraceEntry {
+    pub ip: u32,
+    pub instruction: Instruction,
+    pub src1: u64,
+    pub src2: Option<u64>,
+    pub result: u64,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:08x}: {}  ; src1={:#018x} src2={} -> {:#018x}",
+            self.ip,
+            self.instruction,
+            self.src1,
+            self.src2.map(|v| format!("{v:#018x}")).unwrap_or_else(|| "-".to_string()),
+            self.result
+        )
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -56,6 +89,105 @@ enum Op3 {
     Mod,
     And,
     Hash(u8),
+    AddF,
+    SubF,
+    MulF,
+    DivF,
+}
+
+/// Selects which opcode-decode/execute table a [`VM`] runs.
+///
+/// Each `RomGenerationType` maps to exactly one `IsaGeneration`, chosen once
+/// when the `VM` is created and never changed, so the per-step `match` on it
+/// in `decode_opcode` always takes the same arm.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum IsaGeneration {
+    /// The original integer-only ISA.
+    IntegerV1,
+    /// Adds `AddF`/`SubF`/`MulF`/`DivF` float ops carved out of `Xor`'s range.
+    IntegerFloatV2,
+}
+
+/// Canonical bit pattern every NaN float result is rewritten to before being
+/// written back to a register, so the same program produces the same hash
+/// regardless of which NaN payload the host FPU happened to produce.
+const CANONICAL_NAN_BITS: u64 = 0x7ff8000000000000;
+
+/// Reinterpret the two 64-bit operands as `f64`, apply `op`, and return the
+/// bit pattern to store back in the destination register. NaN results are
+/// canonicalized so every miner computes identical hashes regardless of
+/// platform NaN-payload or sign quirks.
+fn execute_float_op(op: Op3, src1: u64, src2: u64) -> u64 {
+    let a = f64::from_bits(src1);
+    let b = f64::from_bits(src2);
+    let result = match op {
+        Op3::AddF => a + b,
+        Op3::SubF => a - b,
+        Op3::MulF => a * b,
+        Op3::DivF => a / b,
+        _ => unreachable!("execute_float_op called with a non-float Op3"),
+    };
+    if result.is_nan() {
+        CANONICAL_NAN_BITS
+    } else {
+        result.to_bits()
+    }
+}
+
+/// Apply a non-float [`Op3`] to its two operands. `special1_on_zero` is
+/// called instead of dividing when `Div`/`Mod` see a zero divisor -- it
+/// threads in `special1_value64!`'s VM-state read without this function
+/// needing to know about `VM` itself.
+///
+/// Note `Mod` is not a modulo: it computes `src1 / src2`, same as `Div`. This
+/// is a deliberate quirk of the mining ISA, not a bug -- any refactor must
+/// preserve it bit-for-bit or it changes which nonces are valid solutions.
+fn execute_int_op3(op: Op3, src1: u64, src2: u64, special1_on_zero: impl FnOnce() -> u64) -> u64 {
+    match op {
+        Op3::Add => src1.wrapping_add(src2),
+        Op3::Mul => src1.wrapping_mul(src2),
+        Op3::MulH => ((src1 as u128 * src2 as u128) >> 64) as u64,
+        Op3::Xor => src1 ^ src2,
+        Op3::Div => {
+            if src2 == 0 {
+                special1_on_zero()
+            } else {
+                src1 / src2
+            }
+        }
+        Op3::Mod => {
+            if src2 == 0 {
+                special1_on_zero()
+            } else {
+                src1 / src2
+            }
+        }
+        Op3::And => src1 & src2,
+        Op3::AddF | Op3::SubF | Op3::MulF | Op3::DivF => execute_float_op(op, src1, src2),
+        Op3::Hash(v) => {
+            assert!(v < 8);
+            let mut hash_state = Params::new().hash_length(64).to_state();
+            hash_state.update(&src1.to_le_bytes());
+            hash_state.update(&src2.to_le_bytes());
+            let out = hash_state.finalize();
+            match out.as_bytes().chunks(8).nth(v as usize) {
+                Some(chunk) => u64::from_le_bytes(*<&[u8; 8]>::try_from(chunk).unwrap()),
+                None => panic!("chunk doesn't exist"),
+            }
+        }
+    }
+}
+
+/// Apply a non-float [`Op2`] to its operand. `r1` also carries the
+/// rotate amount for `RotL`/`RotR`.
+fn execute_int_op2(op: Op2, src1: u64, r1: u8) -> u64 {
+    match op {
+        Op2::Neg => !src1,
+        Op2::RotL => src1.rotate_left(r1 as u32),
+        Op2::RotR => src1.rotate_right(r1 as u32),
+        Op2::ISqrt => src1.isqrt(),
+        Op2::BitRev => src1.reverse_bits(),
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -69,22 +201,43 @@ enum Op2 {
 
 // special encoding
 
-impl From<u8> for Instr {
-    fn from(value: u8) -> Self {
-        match value {
-            0..40 => Instr::Op3(Op3::Add),                   // 40
-            40..80 => Instr::Op3(Op3::Mul),                  // 40
-            80..96 => Instr::Op3(Op3::MulH),                 // 16
-            96..112 => Instr::Op3(Op3::Div),                 // 16
-            112..128 => Instr::Op3(Op3::Mod),                // 16
-            128..138 => Instr::Op2(Op2::ISqrt),              // 10
-            138..148 => Instr::Op2(Op2::BitRev),             // 10
-            148..188 => Instr::Op3(Op3::Xor),                // 40
-            188..204 => Instr::Op2(Op2::RotL),               // 16
-            204..220 => Instr::Op2(Op2::RotR),               // 16
-            220..240 => Instr::Op2(Op2::Neg),                // 20
-            240..248 => Instr::Op3(Op3::And),                // 8
-            248..=255 => Instr::Op3(Op3::Hash(value - 248)), // 8
+/// Decode an opcode byte under the original, integer-only ISA. The actual
+/// `match` arms are generated by `build.rs` from the `ISA_V1` declarative
+/// weight table, so the byte ranges below always exactly tile `0..=255`.
+fn decode_opcode_v1(value: u8) -> Instr {
+    include!(concat!(env!("OUT_DIR"), "/opcode_v1.rs"))
+}
+
+/// Decode an opcode byte under the floating-point generation: `Xor`'s range
+/// is narrowed from 40 to 24 values to carve out 4 values each for the new
+/// `AddF`/`SubF`/`MulF`/`DivF` ops, keeping every other range identical to
+/// `decode_opcode_v1`. Generated by `build.rs` from the `ISA_V2` table.
+fn decode_opcode_v2(value: u8) -> Instr {
+    include!(concat!(env!("OUT_DIR"), "/opcode_v2.rs"))
+}
+
+fn decode_opcode(value: u8, generation: IsaGeneration) -> Instr {
+    match generation {
+        IsaGeneration::IntegerV1 => decode_opcode_v1(value),
+        IsaGeneration::IntegerFloatV2 => decode_opcode_v2(value),
+    }
+}
+
+impl From<&RomGenerationType> for IsaGeneration {
+    /// Maps a ROM's generation to the ISA it mines with. `RomGenerationType`
+    /// is defined outside this crate (in the `rom` module, included at build
+    /// time rather than living as source here), and only declares `TwoStep`
+    /// today -- so `IntegerFloatV2` is reachable only by passing it directly
+    /// to `VM::new`/`decode_instruction` (the tests below pass it to
+    /// `decode_instruction`), never through this `From` impl. Wiring a real generation to it means adding a variant on
+    /// the `rom` side, which is out of scope for this crate: nothing here
+    /// claims that versioning is complete, only that the ISA side of it is
+    /// ready whenever it is.
+    fn from(generation: &RomGenerationType) -> Self {
+        match generation {
+            RomGenerationType::TwoStep { .. } => IsaGeneration::IntegerV1,
+            #[allow(unreachable_patterns)]
+            _ => IsaGeneration::IntegerV1,
         }
     }
 }
@@ -99,22 +252,21 @@ enum Operand {
 }
 
 impl From<u8> for Operand {
+    /// Decode an operand-kind nibble. The `match` arms are generated by
+    /// `build.rs` from the `OPERAND_SPEC` declarative weight table, so they
+    /// always exactly tile `0..16`.
     fn from(value: u8) -> Self {
         assert!(value <= 0x0f);
-        match value {
-            0..5 => Self::Reg,
-            5..9 => Self::Memory,
-            9..13 => Self::Literal,
-            13..14 => Self::Special1,
-            14.. => Self::Special2,
-        }
+        include!(concat!(env!("OUT_DIR"), "/operand_table.rs"))
     }
 }
 
 impl VM {
     /// Create a new VM which is specific to the ROM by using the RomDigest,
-    /// but mainly dependent on the salt which is an arbitrary byte content
-    pub fn new(rom_digest: &RomDigest, nb_instrs: u32, salt: &[u8]) -> Self {
+    /// but mainly dependent on the salt which is an arbitrary byte content.
+    /// `isa` selects the opcode-decode/execute table once up front; every
+    /// `step`/`execute_one_instruction` then decodes with that same table.
+    pub fn new(rom_digest: &RomDigest, nb_instrs: u32, salt: &[u8], isa: IsaGeneration) -> Self {
         const DIGEST_INIT_SIZE: usize = 64;
         const REGS_CONTENT_SIZE: usize = REGISTER_SIZE * NB_REGS;
 
@@ -151,9 +303,27 @@ impl VM {
             ip: 0,
             loop_counter: 0,
             memory_counter: 0,
+            trace: None,
+            isa,
         }
     }
 
+    /// Like [`VM::new`], but records every executed instruction into a trace
+    /// that can be inspected afterwards with [`VM::trace`]. Recording costs
+    /// an allocation and a push per step, so it must only be opted into
+    /// explicitly; it does not alter `regs`/`prog_digest`/`mem_digest` and so
+    /// cannot change the resulting hash.
+    pub fn new_traced(rom_digest: &RomDigest, nb_instrs: u32, salt: &[u8], isa: IsaGeneration) -> Self {
+        let mut vm = Self::new(rom_digest, nb_instrs, salt, isa);
+        vm.trace = Some(Vec::new());
+        vm
+    }
+
+    /// The recorded trace, if this `VM` was created with [`VM::new_traced`].
+    pub fn trace(&self) -> Option<&[TraceEntry]> {
+        self.trace.as_deref()
+    }
+
     pub fn step(&mut self, rom: &Rom) {
         execute_one_instruction(self, rom);
         self.ip = self.ip.wrapping_add(1);
@@ -249,7 +419,7 @@ impl Program {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct Instruction {
     opcode: Instr,
     op1: Operand,
@@ -261,9 +431,68 @@ pub struct Instruction {
     lit2: u64,
 }
 
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instr::Op3(Op3::Add) => write!(f, "Add"),
+            Instr::Op3(Op3::Mul) => write!(f, "Mul"),
+            Instr::Op3(Op3::MulH) => write!(f, "MulH"),
+            Instr::Op3(Op3::Xor) => write!(f, "Xor"),
+            Instr::Op3(Op3::Div) => write!(f, "Div"),
+            Instr::Op3(Op3::Mod) => write!(f, "Mod"),
+            Instr::Op3(Op3::And) => write!(f, "And"),
+            Instr::Op3(Op3::Hash(n)) => write!(f, "Hash({n})"),
+            Instr::Op3(Op3::AddF) => write!(f, "AddF"),
+            Instr::Op3(Op3::SubF) => write!(f, "SubF"),
+            Instr::Op3(Op3::MulF) => write!(f, "MulF"),
+            Instr::Op3(Op3::DivF) => write!(f, "DivF"),
+            Instr::Op2(Op2::ISqrt) => write!(f, "ISqrt"),
+            Instr::Op2(Op2::Neg) => write!(f, "Neg"),
+            Instr::Op2(Op2::BitRev) => write!(f, "BitRev"),
+            Instr::Op2(Op2::RotL) => write!(f, "RotL"),
+            Instr::Op2(Op2::RotR) => write!(f, "RotR"),
+        }
+    }
+}
+
+impl Operand {
+    /// Render this operand kind given the register/literal it was decoded with.
+    fn render(self, reg: u8, lit: u64) -> String {
+        match self {
+            Operand::Reg => format!("Reg r{reg}"),
+            Operand::Memory => format!("Mem[{lit:#x}]"),
+            Operand::Literal => format!("Lit {lit:#x}"),
+            Operand::Special1 => "Special1".to_string(),
+            Operand::Special2 => "Special2".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.opcode {
+            Instr::Op3(_) => write!(
+                f,
+                "{} {}, {}, r{}",
+                self.opcode,
+                self.op1.render(self.r1, self.lit1),
+                self.op2.render(self.r2, self.lit2),
+                self.r3
+            ),
+            Instr::Op2(_) => write!(
+                f,
+                "{} {}, r{}",
+                self.opcode,
+                self.op1.render(self.r1, self.lit1),
+                self.r3
+            ),
+        }
+    }
+}
+
 #[inline]
-fn decode_instruction(instruction: &[u8; INSTR_SIZE]) -> Instruction {
-    let opcode = Instr::from(instruction[0]);
+fn decode_instruction(instruction: &[u8; INSTR_SIZE], isa: IsaGeneration) -> Instruction {
+    let opcode = decode_opcode(instruction[0], isa);
     let op1 = Operand::from(instruction[1] >> 4);
     let op2 = Operand::from(instruction[1] & 0x0f);
 
@@ -316,6 +545,7 @@ fn execute_one_instruction(vm: &mut VM, rom: &Rom) {
         }};
     }
 
+    let decoded = decode_instruction(&prog_chunk, vm.isa);
     let Instruction {
         opcode,
         op1,
@@ -325,7 +555,7 @@ fn execute_one_instruction(vm: &mut VM, rom: &Rom) {
         r3,
         lit1,
         lit2,
-    } = decode_instruction(&prog_chunk);
+    } = decoded;
 
     match opcode {
         Instr::Op3(operator) => {
@@ -344,39 +574,17 @@ fn execute_one_instruction(vm: &mut VM, rom: &Rom) {
                 Operand::Special2 => special2_value64!(vm),
             };
 
-            let result = match operator {
-                Op3::Add => src1.wrapping_add(src2),
-                Op3::Mul => src1.wrapping_mul(src2),
-                Op3::MulH => ((src1 as u128 * src2 as u128) >> 64) as u64,
-                Op3::Xor => src1 ^ src2,
-                Op3::Div => {
-                    if src2 == 0 {
-                        special1_value64!(vm)
-                    } else {
-                        src1 / src2
-                    }
-                }
-                Op3::Mod => {
-                    if src2 == 0 {
-                        special1_value64!(vm)
-                    } else {
-                        src1 / src2
-                    }
-                }
-                Op3::And => src1 & src2,
-                Op3::Hash(v) => {
-                    assert!(v < 8);
-                    let mut hash_state = Params::new().hash_length(64).to_state();
-                    hash_state.update(&src1.to_le_bytes());
-                    hash_state.update(&src2.to_le_bytes());
-                    let out = hash_state.finalize();
-                    if let Some(chunk) = out.as_bytes().chunks(8).nth(v as usize) {
-                        u64::from_le_bytes(*<&[u8; 8]>::try_from(chunk).unwrap())
-                    } else {
-                        panic!("chunk doesn't exist")
-                    }
-                }
-            };
+            let result = execute_int_op3(operator, src1, src2, || special1_value64!(vm));
+
+            if let Some(trace) = vm.trace.as_mut() {
+                trace.push(TraceEntry {
+                    ip: vm.ip,
+                    instruction: decoded,
+                    src1,
+                    src2: Some(src2),
+                    result,
+                });
+            }
 
             vm.regs[r3 as usize] = result;
         }
@@ -389,13 +597,18 @@ fn execute_one_instruction(vm: &mut VM, rom: &Rom) {
                 Operand::Special2 => special2_value64!(vm),
             };
 
-            let result = match operator {
-                Op2::Neg => !src1,
-                Op2::RotL => src1.rotate_left(r1 as u32),
-                Op2::RotR => src1.rotate_right(r1 as u32),
-                Op2::ISqrt => src1.isqrt(),
-                Op2::BitRev => src1.reverse_bits(),
-            };
+            let result = execute_int_op2(operator, src1, r1);
+
+            if let Some(trace) = vm.trace.as_mut() {
+                trace.push(TraceEntry {
+                    ip: vm.ip,
+                    instruction: decoded,
+                    src1,
+                    src2: None,
+                    result,
+                });
+            }
+
             vm.regs[r3 as usize] = result;
         }
     }
@@ -405,13 +618,99 @@ fn execute_one_instruction(vm: &mut VM, rom: &Rom) {
 pub fn hash(salt: &[u8], rom: &Rom, nb_loops: u32, nb_instrs: u32) -> [u8; 64] {
     assert!(nb_loops >= 2);
     assert!(nb_instrs >= 256);
-    let mut vm = VM::new(&rom.digest, nb_instrs, salt);
+    let isa = IsaGeneration::from(&rom.generation);
+    let mut vm = VM::new(&rom.digest, nb_instrs, salt, isa);
     for _ in 0..nb_loops {
         vm.execute(rom, nb_instrs);
     }
     vm.finalize()
 }
 
+/// Same as [`hash`], but also returns the full per-instruction execution
+/// trace for the run, for reverse-engineering or validating the scavenge
+/// algorithm against the reference implementation. Tracing has no effect on
+/// the returned hash.
+pub fn hash_traced(salt: &[u8], rom: &Rom, nb_loops: u32, nb_instrs: u32) -> ([u8; 64], Vec<TraceEntry>) {
+    assert!(nb_loops >= 2);
+    assert!(nb_instrs >= 256);
+    let isa = IsaGeneration::from(&rom.generation);
+    let mut vm = VM::new_traced(&rom.digest, nb_instrs, salt, isa);
+    for _ in 0..nb_loops {
+        vm.execute(rom, nb_instrs);
+    }
+    let trace = vm.trace().unwrap().to_vec();
+    (vm.finalize(), trace)
+}
+
+/// A 64-bit nonce counter as two 32-bit words, incremented without a branch:
+/// bump the low word, then fold in the carry. The carry is the ANDNOT of the
+/// low word's old and new sign bit -- for a plain `+1` step, that bit can
+/// only flip from `1` to `0` on the one transition that actually wraps
+/// (`u32::MAX` -> `0`), so the trick stays correct exactly at that boundary.
+#[derive(Debug, Clone, Copy)]
+struct NonceCounter {
+    low: u32,
+    high: u32,
+}
+
+impl NonceCounter {
+    fn from_u64(n: u64) -> Self {
+        Self { low: n as u32, high: (n >> 32) as u32 }
+    }
+
+    fn to_u64(self) -> u64 {
+        ((self.high as u64) << 32) | self.low as u64
+    }
+
+    fn increment(&mut self) {
+        let old_high_bit = self.low >> 31;
+        self.low = self.low.wrapping_add(1);
+        let new_high_bit = self.low >> 31;
+        let carry = old_high_bit & !new_high_bit;
+        self.high = self.high.wrapping_add(carry);
+    }
+}
+
+/// Search `count` consecutive nonces starting at `base_nonce` for one whose
+/// digest satisfies `challenge`'s difficulty, returning the first match.
+/// Called once per chunk of a sub-batch in [`crate::execute_mine`] and
+/// [`crate::run_mining_job`], with chunks scanned in parallel via rayon.
+///
+/// This is sequential within one chunk, not SIMD-batched: `hash`'s custom VM
+/// has no vectorized multi-lane primitive the way BLAKE3's `hash_many` does,
+/// so each nonce is still hashed one at a time. What this function actually
+/// batches is the preimage construction -- built once as a template via
+/// [`crate::preimage::build_preimage`], with only the 16-hex-char nonce
+/// region overwritten per iteration instead of reassembling the whole
+/// string -- and the screening, via the allocation-free
+/// [`Difficulty::matches_bytes`](crate::validation::Difficulty::matches_bytes).
+pub fn mine_batch(
+    base_nonce: u64,
+    count: usize,
+    rom: &Rom,
+    address: &str,
+    challenge: &crate::preimage::ChallengeData,
+) -> Option<u64> {
+    let mut counter = NonceCounter::from_u64(base_nonce);
+    let mut preimage = crate::preimage::build_preimage(&format!("{:016x}", counter.to_u64()), address, challenge);
+
+    for _ in 0..count {
+        preimage.replace_range(0..16, &format!("{:016x}", counter.to_u64()));
+
+        let digest = hash(preimage.as_bytes(), rom, 8, 256);
+        let mut prefix32 = [0u8; 32];
+        prefix32.copy_from_slice(&digest[..32]);
+
+        if challenge.difficulty.matches_bytes(&prefix32) {
+            return Some(counter.to_u64());
+        }
+
+        counter.increment();
+    }
+
+    None
+}
+
 pub fn hash_structure_good(hash: &[u8], zero_bits: usize) -> bool {
     let full_bytes = zero_bits / 8; // Number of full zero bytes
     let remaining_bits = zero_bits % 8; // Bits to check in the next byte
@@ -457,6 +756,9 @@ pub struct ChallengeParams {
 pub enum Result {
     Progress(usize),
     Found(u64), // We search for the 64-bit nonce value
+    /// Per-thread throughput, emitted on a fixed wall-clock interval (rather
+    /// than a fixed nonce count) so it stays meaningful across difficulties.
+    Stats { thread_id: u64, tried: u64, hps: f64 },
 }
 
 // Helper to build the preimage string as specified in the API documentation
@@ -496,15 +798,25 @@ fn difficulty_to_zero_bits(difficulty_hex: &str) -> usize {
     zero_bits
 }
 
+/// How often each worker reports [`Result::Stats`], and the smoothing
+/// factor of the exponential moving average used to turn the hashes tried
+/// in that window into a stable hashes-per-second figure.
+const STATS_INTERVAL: Duration = Duration::from_secs(1);
+const STATS_EMA_ALPHA: f64 = 0.25;
+
 // The worker thread function
 fn spin(params: ChallengeParams, sender: Sender<Result>, stop_signal: Arc<AtomicBool>, start_nonce: u64, step_size: u64) {
     let mut nonce_value = start_nonce;
-    const CHUNKS_SIZE: usize = 0xff;
     const NB_LOOPS: u32 = 8;
     const NB_INSTRS: u32 = 256;
 
     let my_address = &params.address;
 
+    let mut tried: u64 = 0;
+    let mut hashes_since_stats: u64 = 0;
+    let mut ema_hps: f64 = 0.0;
+    let mut last_stats_at = std::time::Instant::now();
+
     while !stop_signal.load(Ordering::Relaxed) {
         let preimage_string = build_preimage(
             nonce_value,
@@ -518,6 +830,9 @@ fn spin(params: ChallengeParams, sender: Sender<Result>, stop_signal: Arc<Atomic
         let preimage_bytes = preimage_string.as_bytes();
         let h = hash(preimage_bytes, &params.rom, NB_LOOPS, NB_INSTRS);
 
+        tried += 1;
+        hashes_since_stats += 1;
+
         if hash_structure_good(&h, params.required_zero_bits) {
             if sender.send(Result::Found(nonce_value)).is_ok() {
                 // Sent the found nonce
@@ -525,10 +840,24 @@ fn spin(params: ChallengeParams, sender: Sender<Result>, stop_signal: Arc<Atomic
             return;
         }
 
-        if nonce_value & (CHUNKS_SIZE as u64) == 0 {
-            if sender.send(Result::Progress(CHUNKS_SIZE)).is_err() {
-                 return;
+        let since_last_stats = last_stats_at.elapsed();
+        if since_last_stats >= STATS_INTERVAL {
+            let instant_hps = hashes_since_stats as f64 / since_last_stats.as_secs_f64();
+            ema_hps = if ema_hps == 0.0 {
+                instant_hps
+            } else {
+                STATS_EMA_ALPHA * instant_hps + (1.0 - STATS_EMA_ALPHA) * ema_hps
+            };
+
+            if sender
+                .send(Result::Stats { thread_id: start_nonce, tried, hps: ema_hps })
+                .is_err()
+            {
+                return;
             }
+
+            hashes_since_stats = 0;
+            last_stats_at = std::time::Instant::now();
         }
 
         // Increment nonce by the thread step size
@@ -536,4 +865,307 @@ fn spin(params: ChallengeParams, sender: Sender<Result>, stop_signal: Arc<Atomic
     }
 }
 
-// The main orchestration function
+/// Default number of times `scavenge` retries a submission that failed with a
+/// transport error before giving up and reporting the error to the caller. A
+/// submission the service answers with `Rejected` is not retried.
+const DEFAULT_SUBMIT_RETRIES: u32 = 5;
+
+/// Submit a found solution, retrying transient failures with exponential
+/// backoff up to `max_retries` times.
+fn submit_with_retry<C: SyncClient>(
+    client: &C,
+    nonce: u64,
+    preimage: &str,
+    max_retries: u32,
+) -> std::result::Result<SubmitOutcome, String> {
+    let mut attempt = 0;
+    loop {
+        match client.submit_solution(nonce, preimage) {
+            Ok(outcome) => return Ok(outcome),
+            Err(_) if attempt < max_retries => {
+                thread::sleep(retry_backoff(attempt));
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Aggregate throughput snapshot for a `scavenge` run, recomputed each time
+/// any worker reports fresh [`Result::Stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScavengeStats {
+    /// Sum of the per-thread EMA hash rates, in hashes/second.
+    pub hps: f64,
+    /// Total hashes tried across all threads since the current challenge
+    /// started.
+    pub tried: u64,
+    /// Wall-clock time since the current challenge started.
+    pub elapsed_secs: f64,
+    /// Estimated time to a solution at the current `hps`, assuming a
+    /// uniformly random search over a `2^required_zero_bits` space. `None`
+    /// while `hps` is still zero (no worker has reported yet).
+    pub eta_secs: Option<f64>,
+}
+
+/// The main orchestration function: spawn `nb_threads` [`spin`] workers
+/// against the challenge fetched from `client`, submit any solution found
+/// (re-verifying it locally first and retrying transient submission
+/// failures with backoff), and restart the workers against a fresh
+/// challenge whenever `client.fetch_challenge()` reports one mid-run.
+///
+/// `on_stats` is called on the polling thread every time a worker reports
+/// throughput, with the aggregate across all threads for the challenge
+/// currently being mined.
+///
+/// Returns once a submission is accepted, or the first unrecoverable error.
+pub fn scavenge<C: SyncClient>(
+    client: &C,
+    rom: Arc<Rom>,
+    nb_threads: u64,
+    poll_interval: Duration,
+    mut on_stats: impl FnMut(ScavengeStats),
+) -> std::result::Result<(), String> {
+    let mut info = client.fetch_challenge()?;
+
+    'challenge: loop {
+        let params = info.clone().into_params(Arc::clone(&rom));
+        let stop_signal = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = channel();
+        let mut thread_stats: std::collections::HashMap<u64, (u64, f64)> = std::collections::HashMap::new();
+        let challenge_started_at = std::time::Instant::now();
+
+        let handles: Vec<_> = (0..nb_threads)
+            .map(|i| {
+                let params = params.clone();
+                let sender = sender.clone();
+                let stop_signal = Arc::clone(&stop_signal);
+                thread::spawn(move || spin(params, sender, stop_signal, i, nb_threads))
+            })
+            .collect();
+        drop(sender);
+
+        let mut next_poll_at = std::time::Instant::now() + poll_interval;
+        loop {
+            // Wait only until the next scheduled poll, so steady `Stats`
+            // traffic from the workers can't postpone the challenge check.
+            let wait = next_poll_at.saturating_duration_since(std::time::Instant::now());
+            match receiver.recv_timeout(wait) {
+                Ok(Result::Progress(_)) => continue,
+                Ok(Result::Stats { thread_id, tried, hps }) => {
+                    thread_stats.insert(thread_id, (tried, hps));
+                    let total_tried: u64 = thread_stats.values().map(|(tried, _)| tried).sum();
+                    let total_hps: f64 = thread_stats.values().map(|(_, hps)| hps).sum();
+                    let search_space = 2f64.powi(params.required_zero_bits as i32);
+                    let eta_secs = (total_hps > 0.0 && search_space.is_finite())
+                        .then(|| search_space / total_hps);
+
+                    on_stats(ScavengeStats {
+                        hps: total_hps,
+                        tried: total_tried,
+                        elapsed_secs: challenge_started_at.elapsed().as_secs_f64(),
+                        eta_secs,
+                    });
+                    continue;
+                }
+                Ok(Result::Found(nonce)) => {
+                    stop_signal.store(true, Ordering::Relaxed);
+                    for handle in handles {
+                        let _ = handle.join();
+                    }
+
+                    let preimage = build_preimage(
+                        nonce,
+                        &params.address,
+                        &params.challenge_id,
+                        &params.difficulty_mask,
+                        &params.rom_key,
+                        &params.latest_submission,
+                        &params.no_pre_mine_hour,
+                    );
+                    let rehashed = hash(preimage.as_bytes(), &params.rom, 8, 256);
+                    if !hash_structure_good(&rehashed, params.required_zero_bits) {
+                        return Err("local re-verification of the solution failed".to_string());
+                    }
+
+                    return match submit_with_retry(client, nonce, &preimage, DEFAULT_SUBMIT_RETRIES)? {
+                        SubmitOutcome::Accepted => Ok(()),
+                        SubmitOutcome::Rejected(reason) => Err(format!("submission rejected: {reason}")),
+                    };
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    next_poll_at = std::time::Instant::now() + poll_interval;
+                    if let Ok(fresh) = client.fetch_challenge() {
+                        if fresh.is_fresher_than(&params) {
+                            info = fresh;
+                            stop_signal.store(true, Ordering::Relaxed);
+                            for handle in handles {
+                                let _ = handle.join();
+                            }
+                            continue 'challenge;
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("all scavenge worker threads exited unexpectedly".to_string());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod opcode_table_tests {
+    use super::*;
+
+    #[test]
+    fn v1_opcode_table_covers_every_byte() {
+        for value in 0u8..=255 {
+            let _ = decode_opcode_v1(value);
+        }
+    }
+
+    #[test]
+    fn v2_opcode_table_covers_every_byte() {
+        for value in 0u8..=255 {
+            let _ = decode_opcode_v2(value);
+        }
+    }
+
+    #[test]
+    fn operand_table_covers_every_nibble() {
+        // Exercises the full u8 range, not just the nibble: in-range values
+        // must decode without panicking, and out-of-range values must hit
+        // the `assert!` guard rather than the generated match's `16..=255`
+        // arm silently doing something else -- the match is exhaustive over
+        // `u8` purely for the compiler's sake, not because those values are
+        // ever meant to reach it.
+        let prev_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        for value in 0u8..=255 {
+            let result = std::panic::catch_unwind(|| Operand::from(value));
+            assert_eq!(result.is_ok(), value <= 0x0f, "value {value}");
+        }
+        std::panic::set_hook(prev_hook);
+    }
+}
+
+/// Differential/fuzz coverage for [`decode_instruction`] and the integer ALU
+/// (`execute_int_op3`/`execute_int_op2`).
+///
+/// `hash`/`spin` themselves need a real `Rom` (for `Operand::Memory` reads
+/// and `Rom::digest`), which this tree doesn't have a constructible instance
+/// of -- `rom.rs` is an external, included-at-build dependency, not source
+/// in this crate. So this module stops at the Rom-free boundary: decoding
+/// is pure over instruction bytes, and the ALU is pure over its operands
+/// once `Op3::Div`/`Op3::Mod`'s `special1` fallback is threaded in as a
+/// plain `u64` instead of read from `VM` state.
+#[cfg(test)]
+mod vm_fuzz_tests {
+    use super::*;
+
+    /// A small, dependency-free PRNG (splitmix64) so the fuzz loops below
+    /// are deterministic across runs without pulling in the `rand` crate.
+    struct SplitMix64(u64);
+
+    impl SplitMix64 {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        }
+
+        fn next_bytes(&mut self) -> [u8; INSTR_SIZE] {
+            let mut out = [0u8; INSTR_SIZE];
+            for chunk in out.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next().to_le_bytes()[..chunk.len()]);
+            }
+            out
+        }
+    }
+
+    /// Requirement 1: every one of the 256 opcode bytes and 16 operand
+    /// nibbles decodes without panicking, for every register/literal
+    /// payload a fuzzed instruction word could carry, on both ISA
+    /// generations.
+    #[test]
+    fn decode_instruction_never_panics_for_any_byte_pattern() {
+        let mut rng = SplitMix64(1);
+        for isa in [IsaGeneration::IntegerV1, IsaGeneration::IntegerFloatV2] {
+            for opcode_byte in 0u8..=255 {
+                for _ in 0..8 {
+                    let mut bytes = rng.next_bytes();
+                    bytes[0] = opcode_byte;
+                    let decoded = decode_instruction(&bytes, isa);
+                    // Exercise Display rendering too -- it's the other place
+                    // that pattern-matches on every opcode/operand variant.
+                    let _ = format!("{decoded}");
+                }
+            }
+        }
+    }
+
+    /// Requirement 3: no arithmetic op panics, for any pair of operands
+    /// (including the all-zero and all-one-bits edges `wrapping_*`/`isqrt`/
+    /// rotate amounts need to handle).
+    #[test]
+    fn int_ops_never_panic_across_random_operands() {
+        let mut rng = SplitMix64(2);
+        let op3s = [
+            Op3::Add, Op3::Mul, Op3::MulH, Op3::Xor, Op3::Div, Op3::Mod, Op3::And,
+            Op3::AddF, Op3::SubF, Op3::MulF, Op3::DivF, Op3::Hash(0), Op3::Hash(7),
+        ];
+        let op2s = [Op2::ISqrt, Op2::Neg, Op2::BitRev, Op2::RotL, Op2::RotR];
+
+        let mut operands = vec![(0, 0), (0, u64::MAX), (u64::MAX, 0), (u64::MAX, u64::MAX)];
+        operands.extend((0..1000).map(|_| (rng.next(), rng.next())));
+        for (src1, src2) in operands {
+            for op in op3s {
+                let _ = execute_int_op3(op, src1, src2, || rng.next());
+            }
+            for op in op2s {
+                let _ = execute_int_op2(op, src1, src2 as u8);
+            }
+        }
+    }
+
+    /// Golden vectors pinning the quirky corners of the ALU so a future
+    /// refactor that "fixes" them would be caught here first: `Mod` is
+    /// actually integer division (not modulo), and `Div`/`Mod` by zero both
+    /// fall through to the caller-supplied `special1` value instead of
+    /// panicking.
+    #[test]
+    fn div_and_mod_golden_vectors() {
+        assert_eq!(execute_int_op3(Op3::Div, 100, 7, || panic!("unused")), 14);
+        assert_eq!(execute_int_op3(Op3::Mod, 100, 7, || panic!("unused")), 14);
+        assert_eq!(execute_int_op3(Op3::Div, 100, 0, || 0xDEAD), 0xDEAD);
+        assert_eq!(execute_int_op3(Op3::Mod, 100, 0, || 0xDEAD), 0xDEAD);
+    }
+
+    /// Requirement 2 (partial, Rom-free slice): the ALU and decoder are
+    /// plain functions of their inputs, so the same instruction bytes and
+    /// operands reproduce bit-for-bit across repeated calls -- the
+    /// precondition `hash`'s full bit-for-bit reproducibility (across
+    /// single-threaded and `spin`-threaded runs of the same nonce) builds
+    /// on top of, once a real `Rom` is available to extend this test with.
+    #[test]
+    fn decode_and_alu_are_bit_for_bit_deterministic() {
+        let mut rng = SplitMix64(3);
+        for _ in 0..256 {
+            let bytes = rng.next_bytes();
+            let a = decode_instruction(&bytes, IsaGeneration::IntegerFloatV2);
+            let b = decode_instruction(&bytes, IsaGeneration::IntegerFloatV2);
+            assert_eq!(format!("{a}"), format!("{b}"));
+
+            let src1 = rng.next();
+            let src2 = rng.next();
+            for op in [Op3::Add, Op3::Mul, Op3::Xor, Op3::Div, Op3::MulH] {
+                let r1 = execute_int_op3(op, src1, src2, || 0);
+                let r2 = execute_int_op3(op, src1, src2, || 0);
+                assert_eq!(r1, r2);
+            }
+        }
+    }
+}
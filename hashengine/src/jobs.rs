@@ -0,0 +1,279 @@
+//! Async job queue for `/mine`-shaped requests, replacing the old
+//! 24-hour-keep-alive blocking model: `POST /jobs` enqueues a request and
+//! returns an id immediately; `GET /jobs/{id}` polls
+//! queued/running/done/failed.
+//!
+//! A bounded pool of worker threads (capped at the `workers` count passed to
+//! [`init`]) pulls from a shared queue. Each worker posts a completion
+//! message back over an mpsc channel to a single dispatcher loop, which is
+//! the only thing that ever pops a job off the queue to run it or spawns a
+//! worker ([`drain_queue`] empties it at shutdown without running any) -- so
+//! "a job was submitted" and "a worker just finished" both funnel through
+//! one place, and the active count can never run ahead of the cap.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use log::warn;
+use serde::Serialize;
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+static ACTIVE_WORKERS: AtomicUsize = AtomicUsize::new(0);
+static MAX_WORKERS: AtomicUsize = AtomicUsize::new(1);
+
+/// How many times a transient failure gets retried before a job is marked
+/// [`JobView::Failed`] for real. Overridable via `JOB_MAX_RETRIES` so an
+/// operator can tune it without a rebuild.
+static MAX_RETRIES: AtomicU32 = AtomicU32::new(5);
+
+/// Jobs currently sleeping out a retry backoff, waiting to be re-enqueued.
+static RETRYING_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// Cumulative retry attempts across every job, for `/stats` flap visibility.
+static TOTAL_RETRIES: AtomicU64 = AtomicU64::new(0);
+
+/// Queue depth above which `GET /health/ready` reports unavailable even if a
+/// worker slot is free. Overridable via `JOB_QUEUE_HIGH_WATER_MARK`.
+static QUEUE_HIGH_WATER_MARK: AtomicUsize = AtomicUsize::new(100);
+
+static QUEUE: once_cell::sync::Lazy<Mutex<VecDeque<QueuedJob>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(VecDeque::new()));
+
+static JOBS: once_cell::sync::Lazy<RwLock<HashMap<u64, JobRecord>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(HashMap::new()));
+
+static DISPATCH_TX: once_cell::sync::OnceCell<Sender<DispatchMsg>> = once_cell::sync::OnceCell::new();
+
+struct QueuedJob {
+    id: u64,
+    req: crate::MineRequest,
+    retry_count: u32,
+}
+
+/// Poked whenever the dispatcher might have something new to do: a fresh
+/// submission, or a worker freeing up a slot.
+enum DispatchMsg {
+    Submitted,
+    WorkerDone,
+}
+
+/// A job's status as reported by `GET /jobs/{id}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum JobView {
+    Queued,
+    Running,
+    Done { result: crate::MineResponse },
+    Failed { error: String },
+    /// Never got a worker: the queue was drained for a graceful shutdown
+    /// before this job's turn came up.
+    Aborted,
+}
+
+struct JobRecord {
+    view: JobView,
+    retry_count: u32,
+}
+
+/// A job's status plus how many times it's been retried so far, for
+/// `GET /jobs/{id}` -- flattened so the wire shape is just the existing
+/// `JobView` JSON with one extra `retry_count` field.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatusView {
+    #[serde(flatten)]
+    view: JobView,
+    retry_count: u32,
+}
+
+/// Start the dispatcher loop. Call exactly once, from `main`, before any
+/// `/jobs` request can arrive -- `max_workers` bounds how many run at once,
+/// taken from the same `WORKERS` config the HTTP server itself uses.
+/// `JOB_MAX_RETRIES` (env var, default 5) bounds how many times a
+/// transient failure gets retried before a job is marked failed.
+pub fn init(max_workers: usize) {
+    MAX_WORKERS.store(max_workers.max(1), Ordering::Relaxed);
+    if let Ok(n) = std::env::var("JOB_MAX_RETRIES").unwrap_or_default().parse::<u32>() {
+        MAX_RETRIES.store(n, Ordering::Relaxed);
+    }
+    if let Ok(n) = std::env::var("JOB_QUEUE_HIGH_WATER_MARK").unwrap_or_default().parse::<usize>() {
+        QUEUE_HIGH_WATER_MARK.store(n, Ordering::Relaxed);
+    }
+    let (tx, rx) = mpsc::channel::<DispatchMsg>();
+    DISPATCH_TX.set(tx).ok();
+
+    thread::spawn(move || {
+        for _ in rx {
+            dispatch_next();
+        }
+    });
+}
+
+/// `2^retry_count` seconds, capped at one hour -- the same doubling shape as
+/// `client::retry_backoff`, just with a much longer ceiling since a job
+/// retry is a background reschedule rather than something blocking a
+/// connected caller.
+pub fn retry_sleep_duration(retry_count: i32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(retry_count.max(0) as u32).min(3600))
+}
+
+/// Only "ROM not initialized" is treated as transient -- the no-ROM window
+/// right after a redeploy and before the first `/init` closes on its own,
+/// unlike a malformed `nonce_start` (an `execute_mine` caller error that
+/// retrying won't fix).
+fn is_transient(error: &str) -> bool {
+    error.contains("ROM not initialized")
+}
+
+/// Sleep out `retry_count`'s backoff on a dedicated thread, then re-enqueue
+/// the job and poke the dispatcher -- the sleep doesn't hold a worker slot,
+/// so a backlog of retrying jobs can't starve the pool.
+fn schedule_retry(job: QueuedJob, retry_count: u32) {
+    RETRYING_JOBS.fetch_add(1, Ordering::Relaxed);
+    TOTAL_RETRIES.fetch_add(1, Ordering::Relaxed);
+    set_retry_count(job.id, retry_count);
+    set_view(job.id, JobView::Queued);
+
+    let delay = retry_sleep_duration(retry_count as i32);
+    thread::spawn(move || {
+        thread::sleep(delay);
+        RETRYING_JOBS.fetch_sub(1, Ordering::Relaxed);
+        let mut queue = QUEUE.lock().unwrap();
+        queue.push_back(job);
+        crate::metrics::set_queued_jobs(queue.len());
+        drop(queue);
+        if let Some(tx) = DISPATCH_TX.get() {
+            let _ = tx.send(DispatchMsg::Submitted);
+        }
+    });
+}
+
+/// Hand off as many queued jobs as there are free worker slots for.
+fn dispatch_next() {
+    loop {
+        if ACTIVE_WORKERS.load(Ordering::Relaxed) >= MAX_WORKERS.load(Ordering::Relaxed) {
+            return;
+        }
+        let job = {
+            let mut queue = QUEUE.lock().unwrap();
+            let job = queue.pop_front();
+            crate::metrics::set_queued_jobs(queue.len());
+            match job {
+                Some(job) => job,
+                None => return,
+            }
+        };
+
+        let active = ACTIVE_WORKERS.fetch_add(1, Ordering::Relaxed) + 1;
+        crate::metrics::set_active_jobs(active);
+        set_view(job.id, JobView::Running);
+
+        thread::spawn(move || {
+            let job_start = std::time::Instant::now();
+            match crate::execute_mine(&job.req, &crate::lifecycle::token()) {
+                Ok(result) => {
+                    crate::metrics::inc_completed();
+                    crate::metrics::observe_duration(job_start.elapsed().as_secs_f64());
+                    crate::metrics::inc_cpu_mode(&crate::CPU_MODE.read().unwrap().clone());
+                    set_view(job.id, JobView::Done { result });
+                }
+                Err(error) if is_transient(&error) && job.retry_count < MAX_RETRIES.load(Ordering::Relaxed) => {
+                    crate::metrics::observe_duration(job_start.elapsed().as_secs_f64());
+                    let next_retry = job.retry_count + 1;
+                    warn!("Job {}: transient failure ({error}), scheduling retry {next_retry}", job.id);
+                    schedule_retry(QueuedJob { id: job.id, req: job.req, retry_count: next_retry }, next_retry);
+                }
+                Err(error) => {
+                    crate::metrics::inc_failed();
+                    crate::metrics::observe_duration(job_start.elapsed().as_secs_f64());
+                    crate::metrics::inc_cpu_mode(&crate::CPU_MODE.read().unwrap().clone());
+                    set_view(job.id, JobView::Failed { error });
+                }
+            }
+            let active = ACTIVE_WORKERS.fetch_sub(1, Ordering::Relaxed) - 1;
+            crate::metrics::set_active_jobs(active);
+            if let Some(tx) = DISPATCH_TX.get() {
+                let _ = tx.send(DispatchMsg::WorkerDone);
+            }
+        });
+    }
+}
+
+fn set_view(id: u64, view: JobView) {
+    if let Some(record) = JOBS.write().unwrap().get_mut(&id) {
+        record.view = view;
+    }
+}
+
+fn set_retry_count(id: u64, retry_count: u32) {
+    if let Some(record) = JOBS.write().unwrap().get_mut(&id) {
+        record.retry_count = retry_count;
+    }
+}
+
+/// Enqueue a mining request and return its job id immediately, or `None` if
+/// the process is shutting down and isn't accepting new jobs.
+pub fn submit(req: crate::MineRequest) -> Option<u64> {
+    if crate::lifecycle::is_shutting_down() {
+        return None;
+    }
+
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    JOBS.write().unwrap().insert(id, JobRecord { view: JobView::Queued, retry_count: 0 });
+    let mut queue = QUEUE.lock().unwrap();
+    queue.push_back(QueuedJob { id, req, retry_count: 0 });
+    crate::metrics::set_queued_jobs(queue.len());
+    drop(queue);
+    if let Some(tx) = DISPATCH_TX.get() {
+        let _ = tx.send(DispatchMsg::Submitted);
+    }
+    Some(id)
+}
+
+/// Look up a job's current view (plus retry count) for `GET /jobs/{id}`.
+pub fn get(id: u64) -> Option<JobStatusView> {
+    JOBS.read().unwrap().get(&id).map(|r| JobStatusView { view: r.view.clone(), retry_count: r.retry_count })
+}
+
+/// `(jobs currently sleeping out a retry backoff, cumulative retries across
+/// every job)`, surfaced via `/stats` so operators can see which work is
+/// flapping.
+pub fn retry_stats() -> (usize, u64) {
+    (RETRYING_JOBS.load(Ordering::Relaxed), TOTAL_RETRIES.load(Ordering::Relaxed))
+}
+
+/// Snapshot of the worker pool for `GET /health/ready`: how many workers are
+/// busy, how many jobs are waiting, and whether that adds up to "don't route
+/// more work here" (the pool is fully busy, or the queue has backed up past
+/// its high-water mark).
+pub struct PoolStatus {
+    pub workers_active: usize,
+    pub queue_len: usize,
+    pub saturated: bool,
+}
+
+pub fn pool_status() -> PoolStatus {
+    let workers_active = ACTIVE_WORKERS.load(Ordering::Relaxed);
+    let max_workers = MAX_WORKERS.load(Ordering::Relaxed);
+    let queue_len = QUEUE.lock().unwrap().len();
+    let saturated =
+        workers_active >= max_workers || queue_len > QUEUE_HIGH_WATER_MARK.load(Ordering::Relaxed);
+    PoolStatus { workers_active, queue_len, saturated }
+}
+
+/// Pop every job still sitting in the queue (never assigned a worker) and
+/// mark it [`JobView::Aborted`] instead of running it. Called once graceful
+/// shutdown begins, so a redeploy doesn't wait for an arbitrarily deep
+/// backlog to drain through the worker pool.
+pub fn drain_queue() -> usize {
+    let drained: VecDeque<QueuedJob> = std::mem::take(&mut *QUEUE.lock().unwrap());
+    let count = drained.len();
+    for job in drained {
+        set_view(job.id, JobView::Aborted);
+    }
+    crate::metrics::set_queued_jobs(0);
+    count
+}
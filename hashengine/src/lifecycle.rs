@@ -0,0 +1,29 @@
+//! Process-wide graceful shutdown: one `tokio_util::sync::CancellationToken`
+//! that the SIGINT/SIGTERM handler in `main` cancels, and that every
+//! long-running mining task (the `/mine` and `/start-mining` loops, and the
+//! `/jobs` worker pool) checks at its own safe checkpoints instead of being
+//! hard-killed mid-batch. A redeploy then drains in-flight work cleanly
+//! rather than leaving orphaned CPU-bound threads behind.
+
+use tokio_util::sync::CancellationToken;
+
+static SHUTDOWN: once_cell::sync::Lazy<CancellationToken> = once_cell::sync::Lazy::new(CancellationToken::new);
+
+/// A clone of the process-wide shutdown token -- cheap (a reference-counted
+/// handle), so every long-running task can hold its own and check it at its
+/// own checkpoints.
+pub fn token() -> CancellationToken {
+    SHUTDOWN.clone()
+}
+
+/// True once the SIGINT/SIGTERM handler has begun shutting the process
+/// down. Checked by anything that would otherwise accept new long-running
+/// work (e.g. `POST /jobs`, `POST /start-mining`) so it can refuse instead.
+pub fn is_shutting_down() -> bool {
+    SHUTDOWN.is_cancelled()
+}
+
+/// Begin graceful shutdown: cancel the token so every checkpoint sees it.
+pub fn begin_shutdown() {
+    SHUTDOWN.cancel();
+}
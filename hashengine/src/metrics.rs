@@ -0,0 +1,120 @@
+//! Minimal, dependency-free Prometheus text-exposition metrics for
+//! `GET /metrics`. No metrics crate is pulled in -- just a handful of
+//! atomics (plus one label-keyed map for per-CPU-mode counts and a
+//! fixed-bucket duration histogram behind a `Mutex`), in the same spirit as
+//! the plain `AtomicU64` counters already used for `TOTAL_HASHES` and
+//! `SOLUTIONS_FOUND` in `server.rs`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+
+static ACTIVE_JOBS: AtomicUsize = AtomicUsize::new(0);
+static QUEUED_JOBS: AtomicUsize = AtomicUsize::new(0);
+static JOBS_COMPLETED_TOTAL: AtomicU64 = AtomicU64::new(0);
+static JOBS_FAILED_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+static JOBS_BY_CPU_MODE: once_cell::sync::Lazy<RwLock<HashMap<String, u64>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Upper bound (seconds) of each duration histogram bucket -- Prometheus
+/// `le="..."` buckets are cumulative, so `DURATION_BUCKETS[i]`'s count
+/// includes every observation counted by `DURATION_BUCKETS[i - 1]`.
+const DURATION_BUCKETS: &[f64] = &[0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+struct DurationHistogram {
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+static DURATION_HISTOGRAM: once_cell::sync::Lazy<Mutex<DurationHistogram>> = once_cell::sync::Lazy::new(|| {
+    Mutex::new(DurationHistogram { bucket_counts: vec![0; DURATION_BUCKETS.len()], sum: 0.0, count: 0 })
+});
+
+/// Set the active-mining-jobs gauge.
+pub fn set_active_jobs(n: usize) {
+    ACTIVE_JOBS.store(n, Ordering::Relaxed);
+}
+
+/// Set the queued-mining-jobs gauge.
+pub fn set_queued_jobs(n: usize) {
+    QUEUED_JOBS.store(n, Ordering::Relaxed);
+}
+
+/// Count one more mining job that finished successfully.
+pub fn inc_completed() {
+    JOBS_COMPLETED_TOTAL.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Count one more mining job that ended in an error.
+pub fn inc_failed() {
+    JOBS_FAILED_TOTAL.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Attribute one finished job (successful or failed) to the CPU mode that was
+/// active when it finished.
+pub fn inc_cpu_mode(mode: &str) {
+    let mut counts = JOBS_BY_CPU_MODE.write().unwrap();
+    *counts.entry(mode.to_string()).or_insert(0) += 1;
+}
+
+/// Record one mining job's wall-clock duration into the histogram.
+pub fn observe_duration(secs: f64) {
+    let mut hist = DURATION_HISTOGRAM.lock().unwrap();
+    hist.sum += secs;
+    hist.count += 1;
+    for (upper, bucket) in DURATION_BUCKETS.iter().zip(hist.bucket_counts.iter_mut()) {
+        if secs <= *upper {
+            *bucket += 1;
+        }
+    }
+}
+
+/// Render every metric in Prometheus text exposition format for `GET /metrics`.
+pub fn render() -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP hashengine_active_mining_jobs Mining jobs currently running.\n");
+    out.push_str("# TYPE hashengine_active_mining_jobs gauge\n");
+    out.push_str(&format!("hashengine_active_mining_jobs {}\n", ACTIVE_JOBS.load(Ordering::Relaxed)));
+
+    out.push_str("# HELP hashengine_queued_mining_jobs Mining jobs waiting for a free worker slot.\n");
+    out.push_str("# TYPE hashengine_queued_mining_jobs gauge\n");
+    out.push_str(&format!("hashengine_queued_mining_jobs {}\n", QUEUED_JOBS.load(Ordering::Relaxed)));
+
+    out.push_str("# HELP hashengine_mining_jobs_completed_total Mining jobs that finished successfully.\n");
+    out.push_str("# TYPE hashengine_mining_jobs_completed_total counter\n");
+    out.push_str(&format!(
+        "hashengine_mining_jobs_completed_total {}\n",
+        JOBS_COMPLETED_TOTAL.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP hashengine_mining_jobs_failed_total Mining jobs that ended in an error.\n");
+    out.push_str("# TYPE hashengine_mining_jobs_failed_total counter\n");
+    out.push_str(&format!("hashengine_mining_jobs_failed_total {}\n", JOBS_FAILED_TOTAL.load(Ordering::Relaxed)));
+
+    out.push_str(
+        "# HELP hashengine_mining_jobs_by_cpu_mode_total Finished mining jobs (successful or failed), by the CPU mode active when they finished.\n",
+    );
+    out.push_str("# TYPE hashengine_mining_jobs_by_cpu_mode_total counter\n");
+    for (mode, count) in JOBS_BY_CPU_MODE.read().unwrap().iter() {
+        out.push_str(&format!("hashengine_mining_jobs_by_cpu_mode_total{{cpu_mode=\"{mode}\"}} {count}\n"));
+    }
+
+    out.push_str(
+        "# HELP hashengine_mining_job_duration_seconds Wall-clock duration of mining job runs (successful, failed, or retried).\n",
+    );
+    out.push_str("# TYPE hashengine_mining_job_duration_seconds histogram\n");
+    {
+        let hist = DURATION_HISTOGRAM.lock().unwrap();
+        for (upper, count) in DURATION_BUCKETS.iter().zip(hist.bucket_counts.iter()) {
+            out.push_str(&format!("hashengine_mining_job_duration_seconds_bucket{{le=\"{upper}\"}} {count}\n"));
+        }
+        out.push_str(&format!("hashengine_mining_job_duration_seconds_bucket{{le=\"+Inf\"}} {}\n", hist.count));
+        out.push_str(&format!("hashengine_mining_job_duration_seconds_sum {}\n", hist.sum));
+        out.push_str(&format!("hashengine_mining_job_duration_seconds_count {}\n", hist.count));
+    }
+
+    out
+}
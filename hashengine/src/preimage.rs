@@ -1,9 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+use crate::validation::Difficulty;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChallengeData {
     pub challenge_id: String,
-    pub difficulty: String,
+    pub difficulty: Difficulty,
     pub no_pre_mine: String,
     pub latest_submission: String,
     pub no_pre_mine_hour: String,
@@ -36,7 +38,7 @@ mod tests {
     fn test_build_preimage() {
         let challenge = ChallengeData {
             challenge_id: "**D07C10".to_string(),
-            difficulty: "ffffffff".to_string(),
+            difficulty: Difficulty::try_from("ffffffff").unwrap(),
             no_pre_mine: "e8a195800b".to_string(),
             latest_submission: "abc123".to_string(),
             no_pre_mine_hour: "def456".to_string(),
@@ -55,7 +57,7 @@ mod tests {
     fn test_build_preimage_different_nonce() {
         let challenge = ChallengeData {
             challenge_id: "**D07C10".to_string(),
-            difficulty: "fffffffe".to_string(),
+            difficulty: Difficulty::try_from("fffffffe").unwrap(),
             no_pre_mine: "123456789a".to_string(),
             latest_submission: "submit1".to_string(),
             no_pre_mine_hour: "hour1".to_string(),
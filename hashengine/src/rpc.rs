@@ -0,0 +1,276 @@
+//! A unified JSON-RPC 2.0 surface over the existing REST operations.
+//!
+//! `POST /rpc` wraps `init`, `hash`, `hashBatch`, `mine`, `stats`, and
+//! `setCpuMode` as JSON-RPC 2.0 methods, returning canonical
+//! `{jsonrpc: "2.0", id, result}` / `{jsonrpc: "2.0", id, error: {code,
+//! message}}` envelopes; the error object never carries a `data` member.
+//! [`RpcError`] gives callers a stable numeric
+//! code to branch on instead of string-matching an `ErrorResponse.error`
+//! message -- the existing REST routes are untouched and keep returning
+//! their original ad-hoc error bodies for backward compatibility.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use actix_web::{web, HttpResponse};
+use log::info;
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Stable, machine-readable error codes for the `/rpc` surface. The
+/// `-32600`/`-32601`/`-32602`/`-32700` codes are the standard JSON-RPC 2.0
+/// ones; `-32001`..`-32003` are this service's own reserved server-error
+/// range for the failure modes its mining operations actually hit.
+#[derive(Debug, Clone, Copy)]
+pub enum RpcError {
+    ParseError,
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    RomNotInitialized,
+    InvalidNonce,
+    ValidationFailure,
+    Internal,
+}
+
+impl RpcError {
+    fn code(self) -> i64 {
+        match self {
+            RpcError::ParseError => -32700,
+            RpcError::InvalidRequest => -32600,
+            RpcError::MethodNotFound => -32601,
+            RpcError::InvalidParams => -32602,
+            RpcError::Internal => -32603,
+            RpcError::RomNotInitialized => -32001,
+            RpcError::InvalidNonce => -32002,
+            RpcError::ValidationFailure => -32003,
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            RpcError::ParseError => "Parse error",
+            RpcError::InvalidRequest => "Invalid request",
+            RpcError::MethodNotFound => "Method not found",
+            RpcError::InvalidParams => "Invalid params",
+            RpcError::Internal => "Internal error",
+            RpcError::RomNotInitialized => "ROM not initialized. Call the \"init\" method first.",
+            RpcError::InvalidNonce => "Invalid nonce",
+            RpcError::ValidationFailure => "Difficulty validation failed",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+impl From<RpcError> for RpcErrorObject {
+    fn from(e: RpcError) -> Self {
+        RpcErrorObject { code: e.code(), message: e.message().to_string() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcErrorObject>,
+}
+
+impl RpcResponse {
+    fn ok(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    fn err(id: Value, error: RpcError) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(error.into()) }
+    }
+}
+
+fn current_rom() -> std::result::Result<Arc<crate::Rom>, RpcError> {
+    crate::ROM.read().unwrap().as_ref().cloned().ok_or(RpcError::RomNotInitialized)
+}
+
+/// Shared by `stats_handler` (`GET /stats`) and the `"stats"` RPC method so
+/// the hourly-reset bookkeeping only happens in one place.
+pub(crate) fn compute_stats() -> crate::MiningStatsResponse {
+    let mut reset_lock = crate::LAST_RESET_TIME.write().unwrap();
+    let now = Instant::now();
+
+    let should_reset = match *reset_lock {
+        Some(last_reset) => last_reset.elapsed() >= Duration::from_secs(3600),
+        None => true,
+    };
+
+    if should_reset {
+        info!("Resetting hourly hash counter (prevents overflow)");
+        crate::TOTAL_HASHES.store(0, Ordering::Relaxed);
+        *reset_lock = Some(now);
+    }
+
+    let total_hashes = crate::TOTAL_HASHES.load(Ordering::Relaxed);
+    let solutions_found = crate::SOLUTIONS_FOUND.load(Ordering::Relaxed);
+    let mining_active = crate::MINING_ACTIVE.load(Ordering::Relaxed);
+
+    let hash_rate = if let Some(reset_time) = *reset_lock {
+        let elapsed = reset_time.elapsed().as_secs();
+        if elapsed > 0 { total_hashes / elapsed } else { 0 }
+    } else {
+        0
+    };
+    drop(reset_lock);
+
+    let uptime_seconds =
+        crate::STATS_START_TIME.read().unwrap().map(|t| t.elapsed().as_secs()).unwrap_or(0);
+    let cpu_mode = crate::CPU_MODE.read().unwrap().clone();
+    let (jobs_awaiting_retry, total_job_retries) = crate::jobs::retry_stats();
+
+    crate::MiningStatsResponse {
+        total_hashes,
+        solutions_found,
+        hash_rate,
+        uptime_seconds,
+        mining_active,
+        cpu_mode,
+        jobs_awaiting_retry,
+        total_job_retries,
+        predicted_next_difficulty: crate::validation::predicted_next_difficulty().to_string(),
+    }
+}
+
+async fn do_init(params: Value) -> std::result::Result<Value, RpcError> {
+    let req: crate::InitRequest = serde_json::from_value(params).map_err(|_| RpcError::InvalidParams)?;
+
+    let rom = crate::Rom::new(
+        req.no_pre_mine.as_bytes(),
+        crate::RomGenerationType::TwoStep {
+            pre_size: req.ash_config.pre_size as usize,
+            mixing_numbers: req.ash_config.mixing_numbers as usize,
+        },
+        req.ash_config.rom_size as usize,
+    );
+    *crate::ROM.write().unwrap() = Some(Arc::new(rom));
+
+    if let Some(challenge) = req.challenge.clone() {
+        crate::stratum::publish_new_challenge(challenge).await;
+    }
+
+    Ok(serde_json::json!({
+        "status": "initialized",
+        "worker_pid": std::process::id(),
+        "no_pre_mine": format!("{}...", &req.no_pre_mine[..16.min(req.no_pre_mine.len())]),
+    }))
+}
+
+fn do_hash(params: Value) -> std::result::Result<Value, RpcError> {
+    let req: crate::HashRequest = serde_json::from_value(params).map_err(|_| RpcError::InvalidParams)?;
+    let rom = current_rom()?;
+    let hash_hex = hex::encode(crate::sh_hash(req.preimage.as_bytes(), &rom, 8, 256));
+    Ok(serde_json::json!({ "hash": hash_hex }))
+}
+
+fn do_hash_batch(params: Value) -> std::result::Result<Value, RpcError> {
+    let req: crate::BatchHashRequest = serde_json::from_value(params).map_err(|_| RpcError::InvalidParams)?;
+    if req.preimages.is_empty() {
+        return Err(RpcError::InvalidParams);
+    }
+    let rom = current_rom()?;
+    let hashes: Vec<String> = req
+        .preimages
+        .par_iter()
+        .map(|preimage| hex::encode(crate::sh_hash(preimage.as_bytes(), &rom, 8, 256)))
+        .collect();
+    Ok(serde_json::json!({ "hashes": hashes }))
+}
+
+/// Delegates to [`crate::execute_mine`], the same mining logic `POST /mine`
+/// and the [`crate::jobs`] worker pool run -- so an RPC caller's
+/// `max_millis` budget and `batch_size` are honored identically, and a
+/// process-wide shutdown cancels an in-flight RPC `mine` call exactly like
+/// it would an HTTP one, instead of this method running its own
+/// un-cancellable, un-bounded copy of the same loop.
+fn do_mine(params: Value) -> std::result::Result<Value, RpcError> {
+    let req: crate::MineRequest = serde_json::from_value(params).map_err(|_| RpcError::InvalidParams)?;
+    let response = crate::execute_mine(&req, &crate::lifecycle::token()).map_err(|e| {
+        if e.contains("ROM not initialized") {
+            RpcError::RomNotInitialized
+        } else if e.starts_with("Invalid nonce_start") {
+            RpcError::InvalidNonce
+        } else {
+            RpcError::Internal
+        }
+    })?;
+    serde_json::to_value(response).map_err(|_| RpcError::Internal)
+}
+
+fn do_stats() -> std::result::Result<Value, RpcError> {
+    serde_json::to_value(compute_stats()).map_err(|_| RpcError::Internal)
+}
+
+fn do_set_cpu_mode(params: Value) -> std::result::Result<Value, RpcError> {
+    let req: crate::SetCpuModeRequest = serde_json::from_value(params).map_err(|_| RpcError::InvalidParams)?;
+    let mode = req.mode.to_lowercase();
+    if mode != "max" && mode != "normal" {
+        return Err(RpcError::InvalidParams);
+    }
+
+    *crate::CPU_MODE.write().unwrap() = mode.clone();
+
+    let num_cpus = num_cpus::get();
+    let thread_count = if mode == "max" {
+        std::cmp::max(1, (num_cpus * 9) / 10)
+    } else {
+        std::cmp::max(1, num_cpus / 2)
+    };
+    rayon::ThreadPoolBuilder::new().num_threads(thread_count).build_global().ok();
+
+    info!("CPU mode set to '{}' ({} threads) via /rpc", mode, thread_count);
+
+    Ok(serde_json::json!({ "mode": mode, "thread_count": thread_count }))
+}
+
+async fn dispatch(id: Value, method: String, params: Value) -> RpcResponse {
+    let result = match method.as_str() {
+        "init" => do_init(params).await,
+        "hash" => do_hash(params),
+        "hashBatch" => do_hash_batch(params),
+        "mine" => do_mine(params),
+        "stats" => do_stats(),
+        "setCpuMode" => do_set_cpu_mode(params),
+        _ => Err(RpcError::MethodNotFound),
+    };
+    match result {
+        Ok(value) => RpcResponse::ok(id, value),
+        Err(e) => RpcResponse::err(id, e),
+    }
+}
+
+/// POST /rpc - JSON-RPC 2.0 entry point. Parses the body as raw JSON first
+/// (so a genuinely malformed body gets `ParseError`) and only then checks
+/// for a well-formed `{jsonrpc: "2.0", method, ...}` envelope (`InvalidRequest`
+/// otherwise), mirroring the two-stage validation the spec expects.
+pub async fn rpc_handler(body: web::Bytes) -> HttpResponse {
+    let raw: Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(_) => return HttpResponse::Ok().json(RpcResponse::err(Value::Null, RpcError::ParseError)),
+    };
+
+    let id = raw.get("id").cloned().unwrap_or(Value::Null);
+    let is_v2 = raw.get("jsonrpc").and_then(Value::as_str) == Some("2.0");
+    let method = raw.get("method").and_then(Value::as_str).map(str::to_string);
+
+    let (method, params) = match (is_v2, method) {
+        (true, Some(method)) => (method, raw.get("params").cloned().unwrap_or(Value::Null)),
+        _ => return HttpResponse::Ok().json(RpcResponse::err(id, RpcError::InvalidRequest)),
+    };
+
+    HttpResponse::Ok().json(dispatch(id, method, params).await)
+}
@@ -0,0 +1,295 @@
+//! Stratum-style JSON-RPC-over-TCP work distribution.
+//!
+//! External miners connect over a persistent TCP socket instead of polling
+//! `/mine`: they subscribe, authorize a payout address, and then sit idle
+//! until the server pushes a `mining.notify` carrying a disjoint nonce
+//! window to grind. Found nonces are submitted back over the same socket.
+//!
+//! One line of JSON per request/response/notification. Shares the `ROM` and
+//! stats globals with the HTTP handlers in `server.rs` -- a solution
+//! submitted over Stratum counts toward the same `TOTAL_HASHES`/
+//! `SOLUTIONS_FOUND` atomics a `/start-mining` job does, and each connection's
+//! nonce window is leased from the same [`allocator`] `/mine` and
+//! `/start-mining` use, so `GET /workers` sees Stratum workers too.
+//!
+//! The envelope here is deliberately informal (`{id, method, params}` in,
+//! `{id, result, error}` out) -- upgrading it to a proper JSON-RPC 2.0
+//! envelope with structured error codes is separate follow-up work.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, RwLock};
+
+use crate::allocator;
+use crate::preimage::{build_preimage, ChallengeData};
+use crate::{sh_hash, ROM, SOLUTIONS_FOUND, TOTAL_HASHES};
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Stratum connections authorize with an address, not a caller-chosen
+/// `worker_id` the way `/mine`/`/start-mining` do -- this assigns one at
+/// `mining.authorize` time so [`allocator::record_worker`] has something to
+/// key on. Shares the same `u64` id space as the HTTP workers; a collision
+/// there is no worse than two HTTP callers already reusing the same
+/// `worker_id`, which nothing has ever deduplicated against.
+static NEXT_STRATUM_WORKER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The challenge `mining.notify` is currently advertising, set whenever
+/// `/init` reinstalls the ROM with a fresh challenge. `None` until the first
+/// such `/init` call; connections made before that can subscribe/authorize
+/// but won't receive a job until one arrives.
+static CURRENT_CHALLENGE: once_cell::sync::Lazy<RwLock<Option<ChallengeData>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(None));
+
+/// Broadcasts a fresh challenge to every connected Stratum socket. Each
+/// connection cuts its own job (and disjoint nonce window) off of this when
+/// it relays the broadcast as a `mining.notify`.
+static NOTIFY_CHANNEL: once_cell::sync::Lazy<broadcast::Sender<ChallengeData>> =
+    once_cell::sync::Lazy::new(|| broadcast::channel(16).0);
+
+/// A job handed to exactly one connection: the challenge it was cut from,
+/// plus the `[nonce_start, nonce_end)` window that connection alone owns.
+/// The window itself comes from [`allocator::lease_nonce_window`] -- the same
+/// allocator `/mine` and `/start-mining` lease from -- so a Stratum window
+/// and an HTTP window can never overlap.
+#[derive(Clone)]
+struct Job {
+    job_id: u64,
+    challenge: ChallengeData,
+    nonce_start: u64,
+    nonce_end: u64,
+    cut_at: std::time::Instant,
+}
+
+fn cut_job(challenge: ChallengeData) -> Job {
+    let job_id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    let (nonce_start, nonce_end) = allocator::lease_nonce_window();
+    Job { job_id, challenge, nonce_start, nonce_end, cut_at: std::time::Instant::now() }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl RpcResponse {
+    fn ok(id: Value, result: Value) -> Self {
+        Self { id, result: Some(result), error: None }
+    }
+
+    fn err(id: Value, message: impl Into<String>) -> Self {
+        Self { id, result: None, error: Some(message.into()) }
+    }
+}
+
+/// Per-connection state: the address (if any) authorized on this socket,
+/// the job it was last handed -- `mining.submit` is only valid against that
+/// job's `job_id` and nonce window -- and the `worker_id` assigned at
+/// `mining.authorize` time so [`allocator::record_worker`] has something to
+/// key submissions on.
+#[derive(Default)]
+struct ConnectionState {
+    address: Option<String>,
+    job: Option<Job>,
+    worker_id: Option<u64>,
+}
+
+/// Render a job as a `mining.notify` push (`id: null`, since it's a
+/// server-initiated notification rather than a reply to a request).
+fn notify_line(job: &Job) -> String {
+    let params = serde_json::json!({
+        "job_id": job.job_id,
+        "challenge": job.challenge,
+        "nonce_start": job.nonce_start.to_string(),
+        "nonce_end": job.nonce_end.to_string(),
+    });
+    serde_json::json!({ "id": Value::Null, "method": "mining.notify", "params": params }).to_string()
+}
+
+fn handle_subscribe() -> Value {
+    let id = format!("sub-{:016x}", NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed));
+    serde_json::json!({ "subscription_id": id })
+}
+
+fn handle_authorize(state: &mut ConnectionState, params: &Value) -> std::result::Result<Value, String> {
+    let address = params
+        .get("address")
+        .and_then(Value::as_str)
+        .ok_or("missing \"address\" param")?;
+    state.address = Some(address.to_string());
+    state.worker_id.get_or_insert_with(|| NEXT_STRATUM_WORKER_ID.fetch_add(1, Ordering::Relaxed));
+    Ok(serde_json::json!({ "authorized": true }))
+}
+
+fn handle_submit(state: &ConnectionState, params: &Value) -> std::result::Result<Value, String> {
+    let address = state.address.as_ref().ok_or("not authorized: call mining.authorize first")?;
+    let worker_id = state.worker_id.ok_or("not authorized: call mining.authorize first")?;
+    let job = state.job.as_ref().ok_or("no job assigned yet: wait for mining.notify")?;
+
+    let job_id = params.get("job_id").and_then(Value::as_u64).ok_or("missing \"job_id\" param")?;
+    if job_id != job.job_id {
+        return Ok(serde_json::json!({ "accepted": false, "reason": "stale job_id" }));
+    }
+
+    let nonce: u64 = params
+        .get("nonce")
+        .and_then(Value::as_str)
+        .ok_or("missing \"nonce\" param")?
+        .parse()
+        .map_err(|_| "\"nonce\" must be a decimal u64 string".to_string())?;
+    if nonce < job.nonce_start || nonce >= job.nonce_end {
+        return Ok(serde_json::json!({ "accepted": false, "reason": "nonce outside assigned window" }));
+    }
+
+    let rom = {
+        let rom_lock = ROM.read().unwrap();
+        match rom_lock.as_ref() {
+            Some(r) => Arc::clone(r),
+            None => return Err("ROM not initialized".to_string()),
+        }
+    };
+
+    let nonce_hex = format!("{:016x}", nonce);
+    let preimage = build_preimage(&nonce_hex, address, &job.challenge);
+    let hash_bytes = sh_hash(preimage.as_bytes(), &rom, 8, 256);
+    let mut hash_prefix32 = [0u8; 32];
+    hash_prefix32.copy_from_slice(&hash_bytes[..32]);
+    TOTAL_HASHES.fetch_add(1, Ordering::Relaxed);
+
+    // The server never sees the nonces a Stratum worker tried and rejected
+    // client-side, only the one it submits -- so `nonce - nonce_start` is an
+    // approximation of hashes attempted so far, same as `run_mining_job`
+    // estimating throughput from batch size over elapsed time.
+    let elapsed_secs = job.cut_at.elapsed().as_secs_f64().max(f64::EPSILON);
+    let hashrate = (nonce - job.nonce_start) as f64 / elapsed_secs;
+    allocator::record_worker(worker_id, address.clone(), job.nonce_start, job.nonce_end, hashrate);
+
+    if job.challenge.difficulty.matches_bytes(&hash_prefix32) {
+        SOLUTIONS_FOUND.fetch_add(1, Ordering::Relaxed);
+        crate::validation::record_submission(job.challenge.clone());
+        let hash_hex = hex::encode(hash_bytes);
+        info!("Stratum: {address} submitted a solution for job {job_id} (nonce {nonce})");
+        Ok(serde_json::json!({ "accepted": true, "hash": hash_hex }))
+    } else {
+        Ok(serde_json::json!({ "accepted": false, "reason": "does not meet difficulty" }))
+    }
+}
+
+fn dispatch(state: &mut ConnectionState, req: RpcRequest) -> RpcResponse {
+    let result = match req.method.as_str() {
+        "mining.subscribe" => Ok(handle_subscribe()),
+        "mining.authorize" => handle_authorize(state, &req.params),
+        "mining.submit" => handle_submit(state, &req.params),
+        other => Err(format!("unknown method: {other}")),
+    };
+    match result {
+        Ok(value) => RpcResponse::ok(req.id, value),
+        Err(e) => RpcResponse::err(req.id, e),
+    }
+}
+
+/// Drive one connected socket: read JSON-RPC request lines and write one
+/// response line each, while concurrently relaying `mining.notify` pushes
+/// from [`NOTIFY_CHANNEL`] as fresh challenges arrive.
+async fn handle_connection(socket: tokio::net::TcpStream, peer: std::net::SocketAddr) {
+    let (reader, mut writer) = socket.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut notify_rx = NOTIFY_CHANNEL.subscribe();
+    let mut state = ConnectionState::default();
+
+    // If a challenge is already live, hand this connection its own job
+    // immediately instead of waiting for the next broadcast.
+    if let Some(challenge) = CURRENT_CHALLENGE.read().await.clone() {
+        let job = cut_job(challenge);
+        let line = notify_line(&job);
+        state.job = Some(job);
+        if writer.write_all(format!("{line}\n").as_bytes()).await.is_err() {
+            return;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(line)) if !line.trim().is_empty() => line,
+                    Ok(Some(_)) => continue,
+                    Ok(None) => {
+                        info!("Stratum: {peer} disconnected");
+                        return;
+                    }
+                    Err(e) => {
+                        warn!("Stratum: {peer} read error: {e}");
+                        return;
+                    }
+                };
+
+                let response = match serde_json::from_str::<RpcRequest>(&line) {
+                    Ok(req) => dispatch(&mut state, req),
+                    Err(e) => RpcResponse::err(Value::Null, format!("invalid JSON-RPC request: {e}")),
+                };
+
+                let Ok(encoded) = serde_json::to_string(&response) else { return };
+                if writer.write_all(format!("{encoded}\n").as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+            challenge = notify_rx.recv() => {
+                let challenge = match challenge {
+                    Ok(challenge) => challenge,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                let job = cut_job(challenge);
+                let line = notify_line(&job);
+                state.job = Some(job);
+                if writer.write_all(format!("{line}\n").as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Record a freshly reinstalled ROM's challenge and broadcast a
+/// `mining.notify` to every connected socket. Each connection's previous
+/// job is implicitly invalidated: its `job_id` no longer matches the one
+/// handed out by the new broadcast, so a late `mining.submit` against it is
+/// rejected in [`handle_submit`] as stale.
+pub async fn publish_new_challenge(challenge: ChallengeData) {
+    *CURRENT_CHALLENGE.write().await = Some(challenge.clone());
+    // No connected sockets is not an error -- the channel just has no receivers yet.
+    let _ = NOTIFY_CHANNEL.send(challenge);
+}
+
+/// Accept connections on `addr` and spawn a task per socket. Runs until the
+/// listener itself errors (e.g. the port is already in use).
+pub async fn run(addr: String) -> std::io::Result<()> {
+    let listener = TcpListener::bind(&addr).await?;
+    info!("Stratum: listening on {addr}");
+    loop {
+        let (socket, peer) = listener.accept().await?;
+        info!("Stratum: {peer} connected");
+        tokio::spawn(handle_connection(socket, peer));
+    }
+}
@@ -0,0 +1,115 @@
+//! Per-route request timeouts, replacing one blunt server-wide duration.
+//!
+//! The mining path (`/mine`, `/jobs`, `/start-mining`, ...) needs room for a
+//! long-running batch, but a client hitting a control route --
+//! `/stats`, `/set-cpu-mode`, `/health*` -- has no excuse to hold a socket
+//! anywhere near that long. [`TimeoutConfig`] reads the three knobs from env
+//! vars; [`ControlTimeout`] is a middleware that enforces
+//! `control_request_timeout` on whatever scope it's `.wrap()`ped onto,
+//! independent of the server-wide budget (sized for the mining path) set via
+//! `HttpServer::client_request_timeout`.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use actix_web::body::BoxBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, HttpResponse};
+
+/// The three timeout knobs this service exposes, each overridable via its
+/// own env var so deployment-specific tuning doesn't need a rebuild.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutConfig {
+    /// How long a connection may sit idle between requests before actix
+    /// closes it -- passed to `HttpServer::keep_alive`.
+    pub connect_timeout: Duration,
+    /// The server-wide request budget, sized for the slowest route
+    /// (`/mine`'s own sub-batch loop) rather than a typical control call --
+    /// passed to `HttpServer::client_request_timeout`.
+    pub mining_job_timeout: Duration,
+    /// Enforced only on the control routes (`/stats`, `/set-cpu-mode`,
+    /// `/health*`, `/metrics`) via [`ControlTimeout`] -- short, since none
+    /// of them does any mining work.
+    pub control_request_timeout: Duration,
+}
+
+impl TimeoutConfig {
+    /// Reads `CONNECT_TIMEOUT_SECS` / `MINING_JOB_TIMEOUT_SECS` /
+    /// `CONTROL_REQUEST_TIMEOUT_SECS`, falling back to 75, 300 and 10 seconds
+    /// respectively when a variable is unset or not a whole number.
+    pub fn from_env() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(env_secs("CONNECT_TIMEOUT_SECS", 75)),
+            mining_job_timeout: Duration::from_secs(env_secs("MINING_JOB_TIMEOUT_SECS", 300)),
+            control_request_timeout: Duration::from_secs(env_secs("CONTROL_REQUEST_TIMEOUT_SECS", 10)),
+        }
+    }
+}
+
+fn env_secs(key: &str, default: u64) -> u64 {
+    std::env::var(key).ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(default)
+}
+
+/// Middleware factory: `.wrap(ControlTimeout::new(cfg.control_request_timeout))`
+/// on a scope makes any request inside it that runs longer than `duration`
+/// fail with a 503, instead of holding the connection for the server-wide
+/// (mining-sized) budget.
+pub struct ControlTimeout {
+    duration: Duration,
+}
+
+impl ControlTimeout {
+    pub fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+}
+
+impl<S> Transform<S, ServiceRequest> for ControlTimeout
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = ControlTimeoutMiddleware<S>;
+    type InitError = ();
+    type Future = std::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        std::future::ready(Ok(ControlTimeoutMiddleware { service, duration: self.duration }))
+    }
+}
+
+pub struct ControlTimeoutMiddleware<S> {
+    service: S,
+    duration: Duration,
+}
+
+impl<S> Service<ServiceRequest> for ControlTimeoutMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let duration = self.duration;
+        let http_req = req.request().clone();
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            match tokio::time::timeout(duration, fut).await {
+                Ok(result) => result,
+                Err(_) => Ok(ServiceResponse::new(
+                    http_req,
+                    HttpResponse::ServiceUnavailable()
+                        .json(serde_json::json!({ "error": "control request timed out" })),
+                )),
+            }
+        })
+    }
+}
@@ -8,25 +8,10 @@
 ///
 /// Reference: lib/mining/difficulty.ts lines 79-131
 
-/// Convert difficulty hex string to required zero bits count
+/// Count a mask's leading zero bits (the Heist Engine check's requirement).
 /// Reference TypeScript: difficulty.ts difficultyToZeroBits() lines 19-46
-fn difficulty_to_zero_bits(difficulty_hex: &str) -> Result<usize, String> {
-    // Decode hex string to bytes
-    let bytes = hex::decode(difficulty_hex)
-        .map_err(|e| format!("Failed to decode difficulty hex: {}", e))?;
-
-    let mut zero_bits = 0;
-    for byte in bytes.iter() {
-        if *byte == 0x00 {
-            zero_bits += 8;
-        } else {
-            // Count leading zeros in this byte
-            zero_bits += byte.leading_zeros() as usize;
-            break; // Stop after first non-zero byte
-        }
-    }
-
-    Ok(zero_bits)
+fn mask_to_zero_bits(mask: u32) -> usize {
+    mask.leading_zeros() as usize
 }
 
 /// Check if hash has required leading zero bits
@@ -59,63 +44,326 @@ fn hash_structure_good(hash_bytes: &[u8], zero_bits: usize) -> bool {
     false
 }
 
+/// Allocation-free, bytes-based hot path for the difficulty check -- no hex
+/// decode and no mask re-parsing, for a mining loop that calls this once per
+/// nonce. Only `hash`'s first 4 bytes (the ShadowHarvester prefix) and
+/// however many leading bytes `mask` requires to be zero are ever inspected
+/// -- `mask` is a `u32`, so no real difficulty needs more than 4 leading
+/// zero bytes, but a 32-byte window costs nothing and leaves headroom for a
+/// SIMD-friendly batch size.
+pub fn matches_difficulty_bytes(hash: &[u8; 32], mask: u32) -> bool {
+    // === CHECK 1: ShadowHarvester ((hash | mask) === mask) ===
+    // Primary validation that server uses
+    // Reference: shadowharvester/src/lib.rs:414-417
+    let prefix = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+    if (prefix | mask) != mask {
+        return false;
+    }
+
+    // === CHECK 2: Heist Engine (zero-bits counting) ===
+    // Secondary validation
+    hash_structure_good(hash, mask_to_zero_bits(mask))
+}
+
 /// Main difficulty validation function
 /// Reference TypeScript: difficulty.ts matchesDifficulty() lines 79-131
-pub fn matches_difficulty(hash_hex: &str, difficulty_hex: &str) -> Result<bool, String> {
-    // Validate inputs
+///
+/// Takes an already-validated [`Difficulty`] rather than a raw hex string --
+/// a malformed difficulty is now rejected at parse time (`Difficulty::try_from`),
+/// not deep inside this function. A convenience wrapper around
+/// [`matches_difficulty_bytes`] for callers that still have a hex string:
+/// decodes once, then delegates.
+pub fn matches_difficulty(hash_hex: &str, difficulty: &Difficulty) -> Result<bool, String> {
     if hash_hex.len() < 8 {
         return Err(format!(
             "Invalid hash length: {}, expected at least 8 hex chars",
             hash_hex.len()
         ));
     }
-    if difficulty_hex.len() != 8 {
+
+    let hash_bytes = hex::decode(hash_hex).map_err(|e| format!("Failed to decode hash hex: {}", e))?;
+    if hash_bytes.len() < 32 {
         return Err(format!(
-            "Invalid difficulty length: {}, expected exactly 8 hex chars",
-            difficulty_hex.len()
+            "Invalid hash length: {} bytes, expected at least 32",
+            hash_bytes.len()
         ));
     }
 
-    // Convert hash hex to bytes
-    let hash_bytes = hex::decode(hash_hex)
-        .map_err(|e| format!("Failed to decode hash hex: {}", e))?;
+    let mut hash_prefix32 = [0u8; 32];
+    hash_prefix32.copy_from_slice(&hash_bytes[..32]);
+    Ok(matches_difficulty_bytes(&hash_prefix32, difficulty.0))
+}
 
-    // Extract first 4 bytes (8 hex chars) for ShadowHarvester check
-    let prefix_hex = &hash_hex[..8];
-    let hash_prefix_be = u32::from_str_radix(prefix_hex, 16)
-        .map_err(|e| format!("Failed to parse hash prefix: {}", e))?;
-    let mask = u32::from_str_radix(difficulty_hex, 16)
-        .map_err(|e| format!("Failed to parse difficulty mask: {}", e))?;
+/// A difficulty mask as the bot receives it from the server -- opaque bits,
+/// not a difficulty *value* in its own right. Mirrors rust-bitcoin's `pow`
+/// module: a fixed-width threshold the ShadowHarvester check `(hash_prefix |
+/// mask) == mask` compares the hash prefix against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target(u32);
+
+impl Target {
+    /// Parse an 8-hex-char difficulty string into its mask.
+    pub fn from_difficulty_hex(difficulty_hex: &str) -> Result<Target, String> {
+        if difficulty_hex.len() != 8 {
+            return Err(format!(
+                "Invalid difficulty length: {}, expected exactly 8 hex chars",
+                difficulty_hex.len()
+            ));
+        }
+        let mask = u32::from_str_radix(difficulty_hex, 16)
+            .map_err(|e| format!("Failed to parse difficulty mask: {}", e))?;
+        Ok(Target(mask))
+    }
 
-    // === CHECK 1: ShadowHarvester ((hash | mask) === mask) ===
-    // Primary validation that server uses
-    // Reference: shadowharvester/src/lib.rs:414-417
-    let shadow_harvester_pass = (hash_prefix_be | mask) == mask;
+    /// The inverse work implied by this mask. `(hash_prefix | mask) ==
+    /// mask` passes only when every set bit of the prefix is already set in
+    /// `mask`, which happens for `2^popcount(mask)` of the `2^32` possible
+    /// prefixes -- so the expected number of attempts is `2^(32 -
+    /// popcount(mask))`.
+    pub fn to_work(self) -> Work {
+        Work(1u128 << (32 - self.0.count_ones()))
+    }
 
-    if !shadow_harvester_pass {
-        return Ok(false);
+    /// Synthesizes a mask with exactly `popcount` (clamped to `0..=32`) bits
+    /// set. [`to_work`] depends only on a mask's popcount, not which bits
+    /// are set, so this is the inverse [`predict_next_target`] needs: any
+    /// mask with the right number of bits set implies the right work.
+    fn from_popcount(popcount: u32) -> Target {
+        let popcount = popcount.min(32);
+        Target(if popcount == 0 { 0 } else { u32::MAX >> (32 - popcount) })
     }
+}
 
-    // === CHECK 2: Heist Engine (zero-bits counting) ===
-    // Secondary validation
-    let required_zero_bits = difficulty_to_zero_bits(difficulty_hex)?;
-    let heist_engine_pass = hash_structure_good(&hash_bytes, required_zero_bits);
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:08x}", self.0)
+    }
+}
 
-    // BOTH checks must pass
-    Ok(heist_engine_pass && shadow_harvester_pass)
+impl PartialOrd for Target {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Target {
+    /// Ordered by implied work, not raw mask value -- interior zero bits
+    /// mean a numerically smaller mask isn't always the harder one.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_work().cmp(&other.to_work())
+    }
+}
+
+/// Accumulated expected effort, in hashes -- `2^n` for a single mask, or a
+/// sum of several such terms once a session has worked more than one
+/// challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Work(u128);
+
+impl Work {
+    pub const ZERO: Work = Work(0);
+
+    /// Saturating cast down to `u64` for callers (hash-rate math, ETA
+    /// displays) that don't need `Work`'s full 128-bit range.
+    pub fn to_expected_hashes(self) -> u64 {
+        self.0.min(u64::MAX as u128) as u64
+    }
+}
+
+impl std::ops::Add for Work {
+    type Output = Work;
+    fn add(self, rhs: Work) -> Work {
+        Work(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl std::iter::Sum for Work {
+    fn sum<I: Iterator<Item = Work>>(iter: I) -> Work {
+        iter.fold(Work::ZERO, std::ops::Add::add)
+    }
+}
+
+/// A validated difficulty mask, replacing the bare hex `String` that used to
+/// flow through `ChallengeData` unchecked. Borrows the pattern from tari's
+/// `proof_of_work::difficulty`: parsing happens once, at the boundary
+/// (`TryFrom<&str>`), so a malformed difficulty is rejected when a challenge
+/// is first deserialized rather than deep inside [`matches_difficulty`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Difficulty(u32);
+
+impl Difficulty {
+    /// The easiest possible difficulty: a mask of all ones, where every hash
+    /// prefix passes the ShadowHarvester check.
+    pub const MIN: Difficulty = Difficulty(0xffffffff);
+    /// The hardest possible difficulty: a mask of all zeros, where only an
+    /// all-zero hash prefix passes.
+    pub const MAX: Difficulty = Difficulty(0x00000000);
+
+    fn to_target(self) -> Target {
+        Target(self.0)
+    }
+
+    /// `Some(expected_hashes)` -- this never actually overflows `u64` for a
+    /// 32-bit mask (the largest possible value is `2^32`), but the checked
+    /// form is here so callers don't have to reason about that themselves.
+    pub fn checked_expected_hashes(self) -> Option<u64> {
+        u64::try_from(self.to_target().to_work().0).ok()
+    }
+
+    /// Expected hashes before a solution is found, saturating at `u64::MAX`
+    /// instead of panicking or overflowing.
+    pub fn saturating_expected_hashes(self) -> u64 {
+        self.to_target().to_work().to_expected_hashes()
+    }
+
+    /// Allocation-free hot-path check against an already-computed hash --
+    /// delegates to [`matches_difficulty_bytes`] without making callers reach
+    /// into this difficulty's inner mask themselves.
+    pub fn matches_bytes(self, hash: &[u8; 32]) -> bool {
+        matches_difficulty_bytes(hash, self.0)
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:08x}", self.0)
+    }
 }
 
-/// Calculate expected number of hashes needed based on difficulty
-/// Uses zero-bits counting (more restrictive of the two checks)
+impl TryFrom<&str> for Difficulty {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.len() != 8 {
+            return Err(format!(
+                "Invalid difficulty length: {}, expected exactly 8 hex chars",
+                value.len()
+            ));
+        }
+        let mask = u32::from_str_radix(value, 16)
+            .map_err(|e| format!("Failed to parse difficulty mask: {}", e))?;
+        Ok(Difficulty(mask))
+    }
+}
+
+impl PartialOrd for Difficulty {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Difficulty {
+    /// Ordered by implied work -- harder (more expected hashes) is greater.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_target().cmp(&other.to_target())
+    }
+}
+
+impl serde::Serialize for Difficulty {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Difficulty {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Difficulty::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Calculate expected number of hashes needed based on difficulty.
+///
+/// A thin wrapper over [`Target`]/[`Work`]: expected attempts are driven by
+/// the mask's popcount, not just its leading zeros -- a mask with interior
+/// zero bits needs far more hashes than a leading-zeros count alone implies,
+/// since ShadowHarvester's `(hash_prefix | mask) == mask` check requires
+/// every set bit of the prefix, not just a zero prefix, to line up with `mask`.
 pub fn estimate_hashes_needed(difficulty_hex: &str) -> Result<u64, String> {
-    let zero_bits = difficulty_to_zero_bits(difficulty_hex)?;
+    Ok(Target::from_difficulty_hex(difficulty_hex)?.to_work().to_expected_hashes())
+}
 
-    // Cap at u64::MAX to avoid overflow
-    if zero_bits >= 64 {
-        return Ok(u64::MAX);
+/// How many of the most recent submission intervals [`predict_next_target`]
+/// looks at -- keeps the predictor responsive to a recent retarget instead
+/// of smoothing across an entire session's history.
+const RETARGET_WINDOW: usize = 10;
+
+/// The mean solve time, in seconds, [`predict_next_target`] steers toward.
+const TARGET_SOLVE_SECS: f64 = 30.0;
+
+/// No single observed interval -- however much of an outlier -- can move
+/// the predicted target's implied work more than this factor in either
+/// direction.
+const MAX_RETARGET_FACTOR: f64 = 4.0;
+
+/// Predicts the next challenge's difficulty from recent submission history,
+/// following the "expected nbits" retarget idea from the parity-zcash and
+/// ethash difficulty-adjustment code: observed submission intervals stand
+/// in for block times, and the current [`Target`]'s implied work is scaled
+/// up or down to steer the mean interval toward [`TARGET_SOLVE_SECS`] --
+/// solving faster than the goal raises the next target's work, solving
+/// slower lowers it. A single outlier interval is clamped to
+/// [`MAX_RETARGET_FACTOR`] so it can't swing the prediction on its own.
+///
+/// Falls back to the most recent challenge's own target when there isn't
+/// enough history (fewer than two entries in the retarget window) to derive
+/// an interval.
+pub fn predict_next_target(history: &[(crate::preimage::ChallengeData, u64)]) -> Target {
+    let last = match history.last() {
+        Some((challenge, _)) => challenge.difficulty.to_target(),
+        None => return Target(0xffffffff),
+    };
+
+    let window = &history[history.len().saturating_sub(RETARGET_WINDOW + 1)..];
+    let intervals: Vec<f64> = window
+        .windows(2)
+        .map(|pair| pair[1].1.saturating_sub(pair[0].1) as f64)
+        .filter(|&secs| secs > 0.0)
+        .collect();
+    if intervals.is_empty() {
+        return last;
     }
 
-    Ok(2u64.pow(zero_bits as u32))
+    let mean_interval = intervals.iter().sum::<f64>() / intervals.len() as f64;
+    let factor = (TARGET_SOLVE_SECS / mean_interval).clamp(1.0 / MAX_RETARGET_FACTOR, MAX_RETARGET_FACTOR);
+
+    let scaled_work = ((last.to_work().0 as f64) * factor).max(1.0);
+    let popcount = (32 - scaled_work.log2().floor() as i64).clamp(0, 32) as u32;
+    Target::from_popcount(popcount)
+}
+
+/// Rolling submission history [`predict_next_target`] is actually fed from.
+/// Capped well past [`RETARGET_WINDOW`] (the most the predictor ever looks
+/// at) so it doesn't grow unbounded across a long-running process.
+const HISTORY_CAP: usize = RETARGET_WINDOW * 4;
+
+static SUBMISSION_HISTORY: once_cell::sync::Lazy<std::sync::RwLock<Vec<(crate::preimage::ChallengeData, u64)>>> =
+    once_cell::sync::Lazy::new(|| std::sync::RwLock::new(Vec::new()));
+
+/// Record a just-submitted solution's challenge against the current time, so
+/// the next call to [`predicted_next_difficulty`] reflects it. Called from
+/// every mining hot loop (`run_mining_job`, `execute_mine`,
+/// `stratum::handle_submit`) the moment a solution is found.
+pub fn record_submission(challenge: crate::preimage::ChallengeData) {
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut history = SUBMISSION_HISTORY.write().unwrap();
+    history.push((challenge, unix_secs));
+    if history.len() > HISTORY_CAP {
+        let excess = history.len() - HISTORY_CAP;
+        history.drain(0..excess);
+    }
+}
+
+/// [`predict_next_target`] over the real, accumulated [`SUBMISSION_HISTORY`]
+/// -- the `GET /stats`/`"stats"` RPC field callers read to pre-size a batch
+/// or decide whether the next challenge is worth attempting before spending
+/// any hashes on it.
+pub fn predicted_next_difficulty() -> Target {
+    predict_next_target(&SUBMISSION_HISTORY.read().unwrap())
 }
 
 #[cfg(test)]
@@ -123,27 +371,27 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_difficulty_to_zero_bits() {
-        // All zeros = 32 bits (4 bytes * 8)
-        assert_eq!(difficulty_to_zero_bits("00000000").unwrap(), 32);
+    fn test_mask_to_zero_bits() {
+        // All zeros = 32 bits
+        assert_eq!(mask_to_zero_bits(0x00000000), 32);
 
         // One byte of zeros = 8 bits
-        assert_eq!(difficulty_to_zero_bits("00ffffff").unwrap(), 8);
+        assert_eq!(mask_to_zero_bits(0x00ffffff), 8);
 
         // Two bytes of zeros = 16 bits
-        assert_eq!(difficulty_to_zero_bits("0000ffff").unwrap(), 16);
+        assert_eq!(mask_to_zero_bits(0x0000ffff), 16);
 
         // 0xFF = no leading zeros
-        assert_eq!(difficulty_to_zero_bits("ffffffff").unwrap(), 0);
+        assert_eq!(mask_to_zero_bits(0xffffffff), 0);
 
         // 0x7F = 1 leading zero bit (0111 1111)
-        assert_eq!(difficulty_to_zero_bits("7fffffff").unwrap(), 1);
+        assert_eq!(mask_to_zero_bits(0x7fffffff), 1);
 
         // 0x3F = 2 leading zero bits (0011 1111)
-        assert_eq!(difficulty_to_zero_bits("3fffffff").unwrap(), 2);
+        assert_eq!(mask_to_zero_bits(0x3fffffff), 2);
 
         // 0x1F = 3 leading zero bits (0001 1111)
-        assert_eq!(difficulty_to_zero_bits("1fffffff").unwrap(), 3);
+        assert_eq!(mask_to_zero_bits(0x1fffffff), 3);
     }
 
     #[test]
@@ -177,7 +425,7 @@ mod tests {
         // Any hash should pass
         let result = matches_difficulty(
             "0000000000000000000000000000000000000000000000000000000000000000",
-            "ffffffff"
+            &Difficulty::try_from("ffffffff").unwrap(),
         );
         assert_eq!(result.unwrap(), true);
 
@@ -185,14 +433,14 @@ mod tests {
         // Hash with 32 zero bits should pass both checks
         let result = matches_difficulty(
             "0000000011111111222222223333333344444444555555556666666677777777",
-            "00000000"
+            &Difficulty::try_from("00000000").unwrap(),
         );
         assert_eq!(result.unwrap(), true);
 
         // Hash without enough zero bits should fail
         let result = matches_difficulty(
             "ff00000011111111222222223333333344444444555555556666666677777777",
-            "00000000"
+            &Difficulty::try_from("00000000").unwrap(),
         );
         assert_eq!(result.unwrap(), false);
     }
@@ -207,21 +455,21 @@ mod tests {
         // Hash: 0x00000000... (passes both checks)
         let result = matches_difficulty(
             "0000000011111111222222223333333344444444555555556666666677777777",
-            "7fffffff"
+            &Difficulty::try_from("7fffffff").unwrap(),
         );
         assert_eq!(result.unwrap(), true);
 
         // Hash: 0x7fffffff... (should pass ShadowHarvester but has 1 zero bit)
         let result = matches_difficulty(
             "7fffffff11111111222222223333333344444444555555556666666677777777",
-            "7fffffff"
+            &Difficulty::try_from("7fffffff").unwrap(),
         );
         assert_eq!(result.unwrap(), true);
 
         // Hash: 0x80000000... (fails ShadowHarvester check)
         let result = matches_difficulty(
             "8000000011111111222222223333333344444444555555556666666677777777",
-            "7fffffff"
+            &Difficulty::try_from("7fffffff").unwrap(),
         );
         assert_eq!(result.unwrap(), false);
     }
@@ -241,16 +489,109 @@ mod tests {
         assert_eq!(estimate_hashes_needed("0000f0ff").unwrap(), 1048576);
     }
 
+    #[test]
+    fn test_target_work_popcount() {
+        // Interior zero bits: "0000f0ff" has only 16 *leading* zero bits but
+        // popcount 12, so its implied work is 2^20, not 2^16.
+        let target = Target::from_difficulty_hex("0000f0ff").unwrap();
+        assert_eq!(target.to_work().to_expected_hashes(), 1_048_576);
+
+        // A mask with fewer set bits implies strictly more work.
+        let easy = Target::from_difficulty_hex("ffffffff").unwrap();
+        let hard = Target::from_difficulty_hex("00000000").unwrap();
+        assert!(hard > easy);
+
+        let total = easy.to_work() + hard.to_work();
+        assert_eq!(total.to_expected_hashes(), 1 + (1u64 << 32));
+    }
+
     #[test]
     fn test_invalid_inputs() {
+        let valid = Difficulty::try_from("ffffffff").unwrap();
+
         // Invalid hash length
-        assert!(matches_difficulty("00", "ffffffff").is_err());
+        assert!(matches_difficulty("00", &valid).is_err());
+
+        // Invalid hex characters in the hash
+        assert!(matches_difficulty("gggggggg11111111", &valid).is_err());
+    }
+
+    #[test]
+    fn test_matches_difficulty_bytes_matches_hex_wrapper() {
+        let mut hash = [0x11u8; 32];
+        hash[0] = 0x00;
+        hash[1] = 0x00;
+        hash[2] = 0x00;
+        hash[3] = 0x00;
+        let mask = 0x00000000u32;
+
+        assert!(matches_difficulty_bytes(&hash, mask));
+
+        let hash_hex = hex::encode(hash);
+        let difficulty = Difficulty::try_from("00000000").unwrap();
+        assert_eq!(matches_difficulty(&hash_hex, &difficulty).unwrap(), matches_difficulty_bytes(&hash, mask));
+    }
+
+    #[test]
+    fn test_difficulty_try_from_rejects_malformed_strings() {
+        // Wrong length is rejected at parse time instead of deep inside
+        // matches_difficulty.
+        assert!(Difficulty::try_from("ff").is_err());
+        assert!(Difficulty::try_from("gggggggg").is_err());
+        assert!(Difficulty::try_from("ffffffff").is_ok());
+    }
+
+    #[test]
+    fn test_difficulty_ord_and_display() {
+        assert!(Difficulty::MAX > Difficulty::MIN);
+        assert_eq!(Difficulty::try_from("00ffffff").unwrap().to_string(), "00ffffff");
+        assert_eq!(Difficulty::MIN.saturating_expected_hashes(), 1);
+        assert_eq!(Difficulty::MAX.saturating_expected_hashes(), 1u64 << 32);
+    }
 
-        // Invalid difficulty length
-        assert!(matches_difficulty("0000000011111111", "ff").is_err());
+    fn challenge_with_difficulty(difficulty_hex: &str) -> crate::preimage::ChallengeData {
+        crate::preimage::ChallengeData {
+            challenge_id: "**TEST".to_string(),
+            difficulty: Difficulty::try_from(difficulty_hex).unwrap(),
+            no_pre_mine: "0".to_string(),
+            latest_submission: "0".to_string(),
+            no_pre_mine_hour: "0".to_string(),
+        }
+    }
 
-        // Invalid hex characters
-        assert!(matches_difficulty("gggggggg11111111", "ffffffff").is_err());
-        assert!(matches_difficulty("0000000011111111", "gggggggg").is_err());
+    #[test]
+    fn test_predict_next_target_falls_back_with_no_history() {
+        assert_eq!(predict_next_target(&[]), Target(0xffffffff));
+    }
+
+    #[test]
+    fn test_predict_next_target_falls_back_with_one_entry() {
+        let history = [(challenge_with_difficulty("0000ffff"), 1_000u64)];
+        assert_eq!(predict_next_target(&history), Target(0x0000ffff));
+    }
+
+    #[test]
+    fn test_predict_next_target_raises_difficulty_when_solving_fast() {
+        // Every interval is 1 second, far under the 30-second goal, so the
+        // predicted target should imply strictly more work than the last one.
+        let history: Vec<_> = (0..5)
+            .map(|i| (challenge_with_difficulty("0000ffff"), i as u64))
+            .collect();
+        let predicted = predict_next_target(&history);
+        let last = Target::from_difficulty_hex("0000ffff").unwrap();
+        assert!(predicted.to_work() > last.to_work());
+    }
+
+    #[test]
+    fn test_predict_next_target_clamps_outlier_interval() {
+        // A single wildly slow interval can't push the predicted work below
+        // last/MAX_RETARGET_FACTOR.
+        let history = [
+            (challenge_with_difficulty("0000ffff"), 0u64),
+            (challenge_with_difficulty("0000ffff"), 100_000u64),
+        ];
+        let predicted = predict_next_target(&history);
+        let last = Target::from_difficulty_hex("0000ffff").unwrap();
+        assert_eq!(predicted.to_work().to_expected_hashes(), last.to_work().to_expected_hashes() / 4);
     }
 }